@@ -0,0 +1,23 @@
+use std::env;
+
+use rsille::extra::{LifeEditor, LifeGame};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let lg = match args.get(1) {
+        Some(path) => match LifeGame::from_path(path) {
+            Ok(lg) => lg,
+            Err(_) => {
+                println!("can't parse {}!", path);
+                return;
+            }
+        },
+        None => LifeGame::new(),
+    };
+    let mut editor = LifeEditor::new(lg);
+    if let Err(e) = editor.run() {
+        println!("{}", e);
+        return;
+    }
+    println!("{}", editor.status());
+}
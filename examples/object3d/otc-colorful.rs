@@ -65,11 +65,7 @@ fn main() {
             obj.rotate(angle);
             obj.zoom(zoom);
             k += 1;
-            if k >= 300 {
-                true
-            } else {
-                false
-            }
+            k >= 300
         },
         (1.6 * side_len, 1.6 * side_len),
     );
@@ -1,19 +1,23 @@
 use std::{
     io::Write,
+    panic,
     sync::{Arc, Mutex},
     thread,
     time::{Duration, Instant},
 };
 
 use crossterm::{
-    cursor::MoveTo,
+    cursor::{self, MoveTo},
     event::{Event, KeyCode, KeyModifiers},
     queue,
-    terminal::{disable_raw_mode, enable_raw_mode},
+    style::Print,
+    terminal::{enable_raw_mode, Clear, ClearType},
 };
 
 use crate::{
-    term::{self, get_terminal_size, is_raw_mode},
+    motion,
+    record::Recorder,
+    term::{self, get_terminal_size},
     Canvas, Paint,
 };
 
@@ -39,10 +43,24 @@ pub struct Animation {
     objs: Arc<Mutex<Vec<Box<dyn Update + Send>>>>,
     fps: u32,
     hide_cursor: bool,
-    size: Option<(i32, i32)>,
+    // the min (cols, rows) of the terminal
+    min_size: Option<(u16, u16)>,
+    position: Position,
+    recorder: Arc<Mutex<Option<Recorder>>>,
     end: Arc<Mutex<bool>>,
 }
 
+/// Where the [`Animation`](struct.Animation.html) is painted on the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Position {
+    /// Clear the whole screen and paint from the left top corner, it's the default
+    Full,
+    /// Paint with the left top corner on *(col, row)*, the rest of the screen is kept
+    At(u16, u16),
+    /// Paint below the cursor like the normal output, and the shell goes on below it after the end
+    Inline,
+}
+
 impl Animation {
     /// Create a new animation
     ///
@@ -53,7 +71,9 @@ impl Animation {
             objs: Arc::new(Mutex::new(Vec::new())),
             fps: 30,
             hide_cursor: true,
-            size: None,
+            min_size: None,
+            position: Position::Full,
+            recorder: Arc::new(Mutex::new(None)),
             end: Arc::new(Mutex::new(false)),
         }
     }
@@ -81,55 +101,107 @@ impl Animation {
     /// Run the animation
     ///
     /// When all the objects are end or press `ctrl+c` or `esc`, the animation will stop.
+    /// Press `ctrl+z` to suspend it like other programs, and it goes on after `fg`.
+    ///
+    /// If any object panics, the terminal is restored before the panic message is shown,
+    /// and the panic goes on after the animation stops.
     pub fn run(&mut self) {
         // should be very carefully to change these code
 
+        // restore the terminal before the panic message, or it's messed up by the raw mode
+        let prev_hook = Arc::new(panic::take_hook());
+        let hook = Arc::clone(&prev_hook);
+        panic::set_hook(Box::new(move |info| {
+            term::restore();
+            hook(info);
+        }));
+
         // init
-        let duration = Duration::from_secs(1) / self.fps;
+        let fps = motion::max_fps().map_or(self.fps, |max| self.fps.min(max));
+        let duration = Duration::from_secs(1) / fps.max(1);
         let objs = Arc::clone(&self.objs);
         let canvas = Arc::clone(&self.canvas);
+        let recorder = Arc::clone(&self.recorder);
+        let min_size = self.min_size;
         let mut stdout = std::io::stdout();
-        term::clear();
+        let position = self.position;
+        if position == Position::Full {
+            term::clear();
+        }
         if self.hide_cursor {
             term::hide_cursor();
         }
         enable_raw_mode().expect("can't enbale raw mode");
+        let mut origin = match position {
+            Position::Full => (0, 0),
+            Position::At(col, row) => (col, row),
+            Position::Inline => (0, cursor::position().map_or(0, |(_, row)| row)),
+        };
 
         // main loop
         let end = Arc::clone(&self.end);
-        let mainloop = thread::spawn(move || loop {
-            let start_time = Instant::now();
-            // must wraped! for drop the objs
-            {
-                let mut objs = objs.lock().unwrap();
-                let mut end = end.lock().unwrap();
-                if *end {
-                    break;
-                }
-                if objs.iter().all(|obj| obj.is_end()) {
-                    *end = true;
-                    break;
+        let mainloop = thread::spawn(move || {
+            // the message of too small terminal on the screen
+            let mut shown = None;
+            loop {
+                let start_time = Instant::now();
+                // must wraped! for drop the objs
+                {
+                    let mut objs = objs.lock().unwrap();
+                    let mut end = end.lock().unwrap();
+                    if *end {
+                        break;
+                    }
+                    if objs.iter().all(|obj| obj.is_end()) {
+                        *end = true;
+                        break;
+                    }
+                    // pause and tell the user if the terminal is too small
+                    if let Some(msg) = too_small(min_size) {
+                        if shown.as_ref() != Some(&msg) {
+                            let rows = canvas.lock().unwrap().rows();
+                            show_message(&mut stdout, &msg, position, origin, rows);
+                            shown = Some(msg);
+                        }
+                    } else {
+                        let mut canvas = canvas.lock().unwrap();
+                        if shown.take().is_some() {
+                            clear_area(&mut stdout, position, origin, canvas.rows());
+                        }
+                        canvas.clear();
+                        for obj in &mut *objs {
+                            obj.update(); // shouldn't wrap with if obj.is_end() { ... }
+                            obj.paint(&mut canvas);
+                        }
+                        if position == Position::Inline {
+                            origin.1 = scroll_for(&mut stdout, origin.1, canvas.rows());
+                        }
+                        canvas.print_at(&mut stdout, origin.0, origin.1).unwrap();
+                        stdout.flush().unwrap();
+                        if let Some(recorder) = recorder.lock().unwrap().as_mut() {
+                            recorder.record(&canvas);
+                        }
+                    }
                 }
-                let mut canvas = canvas.lock().unwrap();
-                canvas.clear();
-                queue!(stdout, MoveTo(0, 0)).unwrap();
-                for obj in &mut *objs {
-                    obj.update(); // shouldn't wrap with if obj.is_end() { ... }
-                    obj.paint(&mut canvas);
+                let elapsed = start_time.elapsed();
+                if elapsed < duration {
+                    thread::sleep(duration - elapsed);
                 }
-                // canvas.print_on(&mut stdout, true).unwrap();
-                canvas.print();
-                stdout.flush().unwrap();
             }
-            let elapsed = start_time.elapsed();
-            if elapsed < duration {
-                thread::sleep(duration - elapsed);
+            // let the shell go on below the animation
+            if position == Position::Inline {
+                let rows = canvas.lock().unwrap().rows();
+                // the new line scrolls the screen if the animation is at the bottom
+                let last = origin.1.saturating_add(rows.saturating_sub(1));
+                queue!(stdout, MoveTo(0, last), Print("\r\n")).unwrap();
+                stdout.flush().unwrap();
             }
         });
 
         // deal with the key
         let objs = Arc::clone(&self.objs);
         let end = Arc::clone(&self.end);
+        let hide_cursor = self.hide_cursor;
         let _keyloop = thread::spawn(move || loop {
             if *end.lock().unwrap() {
                 break;
@@ -145,7 +217,9 @@ impl Animation {
                     *end = true;
                 };
                 if let Event::Resize(_, _) = event {
-                    term::clear();
+                    if position == Position::Full {
+                        term::clear();
+                    }
                 }
                 if let Event::Key(key) = event {
                     if key.code == KeyCode::Esc {
@@ -156,23 +230,69 @@ impl Animation {
                         end_fn();
                         break;
                     }
+                    if key.code == KeyCode::Char('z') && key.modifiers == KeyModifiers::CONTROL {
+                        // hold the objs, so the main loop won't draw while suspended
+                        let _objs = objs.lock().unwrap();
+                        suspend(hide_cursor, position);
+                    }
                 }
             }
         });
 
         // keyloop.join().unwrap();
-        mainloop.join().unwrap();
-        term::show_cursor();
-        disable_raw_mode().expect("can't disable raw mode");
+        let result = mainloop.join();
+        term::restore();
+        // our hook holds the other one, drop it first
+        drop(panic::take_hook());
+        if let Ok(prev_hook) = Arc::try_unwrap(prev_hook) {
+            panic::set_hook(prev_hook);
+        }
+        if let Err(e) = result {
+            // let the panic go on
+            panic::resume_unwind(e);
+        }
     }
 
     /// Set the fps of animation
     ///
-    /// Default is 30
+    /// Default is 30, and it can't be higher than [motion::max_fps](crate::motion::max_fps)
     pub fn set_fps(&mut self, fps: u32) {
         self.fps = fps;
     }
 
+    /// Set where to paint on the terminal
+    ///
+    /// Give a look at [`Position`](enum.Position.html)
+    pub fn set_position(&mut self, position: Position) {
+        self.position = position;
+    }
+
+    /// Record every frame or not, the default is `false`
+    ///
+    /// Get the frames by [`take_record`](struct.Animation.html#method.take_record) after running
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use rsille::{extra::Object3D, Animation};
+    /// let mut anime = Animation::new();
+    /// anime.push(Object3D::cube(30.0), |cube| {
+    ///     cube.rotate((1.0, 2.0, 3.0));
+    ///     false
+    /// }, (30, -30));
+    /// anime.record(true);
+    /// anime.run();
+    /// anime.take_record().unwrap().save_asciicast("cube.cast").unwrap();
+    /// ```
+    pub fn record(&mut self, record: bool) {
+        *self.recorder.lock().unwrap() = if record { Some(Recorder::new()) } else { None };
+    }
+
+    /// Take the recorded frames, the animation won't record after taking
+    pub fn take_record(&mut self) -> Option<Recorder> {
+        self.recorder.lock().unwrap().take()
+    }
+
     /// Hide the cursor or not
     pub fn hide_cursor(&mut self, hide_cursor: bool) {
         self.hide_cursor = hide_cursor;
@@ -204,27 +324,108 @@ impl Animation {
         self.canvas.lock().unwrap().set_maxy(maxy);
     }
 
-    // only used in run!
-    #[allow(unused)]
-    fn check_size(&self) {
-        let size = if let Some(size) = self.size {
-            size
-        } else {
-            return;
-        };
-        if !is_raw_mode() {
-            return;
-        }
-        let (rows, cols) = get_terminal_size();
-        if (rows as i32) < size.0 || (cols as i32) < size.1 {
-            println!(
-                "this anime need at least {}x{} terminal size, but only {}x{}",
-                size.1, size.0, cols, rows
-            );
-        }
+    /// Set the min size of the terminal
+    ///
+    /// When the terminal is smaller, the animation is paused and a message is shown,
+    /// and it goes on when the terminal is resized back.
+    pub fn set_min_size(&mut self, cols: u16, rows: u16) {
+        self.min_size = Some((cols, rows));
+    }
+}
+
+// the message if the terminal is smaller than the min size
+fn too_small(min_size: Option<(u16, u16)>) -> Option<String> {
+    let (min_cols, min_rows) = min_size?;
+    let (cols, rows) = get_terminal_size();
+    if cols >= min_cols && rows >= min_rows {
+        return None;
     }
+    Some(format!(
+        "the terminal is too small, it needs {}x{} but it's {}x{}",
+        min_cols, min_rows, cols, rows
+    ))
 }
 
+// the full screen one shows it in the center, the others only use their own rows
+fn show_message(
+    stdout: &mut impl Write,
+    msg: &str,
+    position: Position,
+    origin: (u16, u16),
+    rows: u16,
+) {
+    clear_area(stdout, position, origin, rows);
+    let (x, y) = if position == Position::Full {
+        let (cols, rows) = get_terminal_size();
+        (
+            cols.saturating_sub(msg.chars().count() as u16) / 2,
+            rows / 2,
+        )
+    } else {
+        origin
+    };
+    queue!(stdout, MoveTo(x, y), Print(msg)).unwrap();
+    stdout.flush().unwrap();
+}
+
+// clear the screen of the full screen one, or the rows of the animation, the rest of the screen is kept
+fn clear_area(stdout: &mut impl Write, position: Position, origin: (u16, u16), rows: u16) {
+    if position == Position::Full {
+        term::clear();
+        return;
+    }
+    let (_, height) = get_terminal_size();
+    let last = origin.1.saturating_add(rows.max(1)).min(height);
+    for row in origin.1..last {
+        queue!(
+            stdout,
+            MoveTo(origin.0, row),
+            Clear(ClearType::UntilNewLine)
+        )
+        .unwrap();
+    }
+    stdout.flush().unwrap();
+}
+
+// scroll the screen up if the rows from the top can't be shown, return the new top
+fn scroll_for(stdout: &mut impl Write, top: u16, rows: u16) -> u16 {
+    let (_, height) = get_terminal_size();
+    let overflow = (top as u32 + rows as u32).saturating_sub(height as u32) as u16;
+    let overflow = overflow.min(top);
+    if overflow > 0 {
+        queue!(
+            stdout,
+            MoveTo(0, height - 1),
+            Print("\n".repeat(overflow as usize))
+        )
+        .unwrap();
+    }
+    top - overflow
+}
+
+// the raw mode eats the ctrl+z, so restore the terminal and stop by ourself
+#[cfg(unix)]
+fn suspend(hide_cursor: bool, position: Position) {
+    term::restore();
+    // SAFETY: raise is async-signal-safe, the default action of SIGTSTP just stops the process
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+    // continued by SIGCONT, the screen may be used by others, redraw all,
+    // but only the full screen one owns the screen, the others are just drawn again on the next frame
+    if position == Position::Full {
+        term::clear();
+    }
+    if hide_cursor {
+        term::hide_cursor();
+    }
+    enable_raw_mode().expect("can't enbale raw mode");
+}
+
+// there isn't job control
+#[cfg(not(unix))]
+fn suspend(_hide_cursor: bool, _position: Position) {}
+
 struct UserObj<T, F> {
     obj: T,
     f: F,
@@ -18,6 +18,140 @@ const PIXEL_MAP: [[u32; 2]; 4] = [[0x01, 0x08],
                                   [0x40, 0x80]];
 // braille unicode characters starts at 0x2800
 const BASE_CHAR: u32 = 0x2800;
+// the sextants starts at 0x1FB00, but the left half, the right half and the full block are missing
+const SEXTANT_CHAR: u32 = 0x1FB00;
+// bits: 1 is left top, 2 is right top, 4 is left bottom, 8 is right bottom
+#[rustfmt::skip]
+const QUADRANTS: [char; 16] = [' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛',
+                               '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█'];
+#[rustfmt::skip]
+const ASCII: [char; 16] = [' ', '`', '\'', '"', ',', '|', '/', 'F',
+                           '.', '\\', '|', '7', '_', 'L', 'J', '#'];
+
+/// The dots in a cell of the terminal and the chars to show them
+///
+/// The [`Canvas`](crate::Canvas) holds one of it, chosen at runtime by [`set_glyphs`](crate::Canvas::set_glyphs).
+/// It puts the dots into the cells by the [`size`](trait.DotMatrix.html#tymethod.size),
+/// and keeps the dots of a cell in the bits of a `u32`.
+/// So all the drawing on the canvas, like the lines, the plots and the 3D objects,
+/// works at the native resolution of the glyphs.
+/// The [`Glyphs`](enum.Glyphs.html) are the built-in ones, implement it for your own chars.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{DotMatrix, Glyphs};
+/// assert_eq!(Glyphs::Sextant.size(), (2, 3));
+/// let top = Glyphs::Sextant.bit(0, 0) | Glyphs::Sextant.bit(1, 0);
+/// assert_eq!(Glyphs::Sextant.glyph(top), '🬂');
+/// assert_eq!(Glyphs::HalfBlock.glyph(Glyphs::HalfBlock.bit(0, 1)), '▄');
+/// ```
+///
+/// draw with one big dot in a cell
+/// ```
+/// use rsille::{Canvas, DotMatrix};
+///
+/// #[derive(Debug)]
+/// struct Big;
+///
+/// impl DotMatrix for Big {
+///     fn size(&self) -> (u8, u8) {
+///         (1, 1)
+///     }
+///
+///     fn glyph(&self, bits: u32) -> char {
+///         if bits == 0 { ' ' } else { '●' }
+///     }
+/// }
+///
+/// let mut c = Canvas::new();
+/// c.set_glyphs(Big);
+/// c.line((0, 0), (10, 5));
+/// assert_eq!(c.glyphs().size(), (1, 1));
+/// c.print();
+/// ```
+pub trait DotMatrix: fmt::Debug + Send + Sync {
+    /// Return the *(columns, rows)* of the dots in a cell, *8* dots at most
+    fn size(&self) -> (u8, u8);
+
+    /// Return the bit of the dot in a cell, *(0, 0)* is the left top dot
+    ///
+    /// The default is one bit for every dot, row by row from the left top
+    fn bit(&self, col: u8, row: u8) -> u32 {
+        1 << (row * self.size().0 + col)
+    }
+
+    /// Return the char of a cell with the dots in the `bits`
+    fn glyph(&self, bits: u32) -> char;
+
+    /// Return the char of the empty cell
+    fn space(&self) -> char {
+        ' '
+    }
+}
+
+/// The chars used to show the dots of a cell
+///
+/// Every glyphs has its own [dots in a cell](trait.DotMatrix.html),
+/// the less dots the bigger they are.
+/// It's for the fonts and terminals can't show the braille well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Glyphs {
+    /// The braille, 2x4 dots in a cell
+    Braille,
+    /// The sextant blocks of the unicode 13, 2x3 dots in a cell
+    Sextant,
+    /// The quadrant blocks, 2x2 dots in a cell
+    Quadrant,
+    /// The half blocks, 1x2 dots in a cell
+    HalfBlock,
+    /// Only the ascii chars like the quadrants, it works everywhere
+    Ascii,
+}
+
+impl DotMatrix for Glyphs {
+    fn size(&self) -> (u8, u8) {
+        match self {
+            Glyphs::Braille => (2, 4),
+            Glyphs::Sextant => (2, 3),
+            Glyphs::Quadrant | Glyphs::Ascii => (2, 2),
+            Glyphs::HalfBlock => (1, 2),
+        }
+    }
+
+    fn bit(&self, col: u8, row: u8) -> u32 {
+        match self {
+            Glyphs::Braille => PIXEL_MAP[row as usize][col as usize],
+            _ => 1 << (row * self.size().0 + col),
+        }
+    }
+
+    fn glyph(&self, bits: u32) -> char {
+        match self {
+            Glyphs::Braille => make_braille_unchecked(bits & 0xFF),
+            Glyphs::Sextant => match bits & 63 {
+                0 => ' ',
+                21 => '▌',
+                42 => '▐',
+                63 => '█',
+                p => {
+                    let skip = (p > 21) as u32 + (p > 42) as u32;
+                    char::from_u32(SEXTANT_CHAR + p - 1 - skip).unwrap_or(' ')
+                }
+            },
+            Glyphs::Quadrant => QUADRANTS[(bits & 15) as usize],
+            Glyphs::HalfBlock => [' ', '▀', '▄', '█'][(bits & 3) as usize],
+            Glyphs::Ascii => ASCII[(bits & 15) as usize],
+        }
+    }
+
+    fn space(&self) -> char {
+        match self {
+            Glyphs::Braille => SPACE,
+            _ => ' ',
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
 pub struct Pixel {
@@ -32,38 +166,32 @@ impl Pixel {
     pub unsafe fn from_unchecked(code: u32) -> Self {
         Self { code }
     }
-}
 
-pub trait PixelOp<T>
-where
-    T: Into<f64> + Copy,
-{
-    fn unset(&mut self, x: T, y: T);
-    fn set(&mut self, x: T, y: T);
-    fn toggle(&mut self, x: T, y: T);
-}
+    pub fn code(&self) -> u32 {
+        self.code
+    }
 
-impl<T> PixelOp<T> for Pixel
-where
-    T: Into<f64> + Copy,
-{
-    fn unset(&mut self, x: T, y: T) {
-        let p = get_pixel(x, y);
-        self.code &= !p;
+    // the row 0 is the top row of the braille code
+    pub fn is_set(&self, col: usize, row: usize) -> bool {
+        self.code & PIXEL_MAP[row][col] != 0
     }
 
-    fn set(&mut self, x: T, y: T) {
-        let p = get_pixel(x, y);
-        self.code |= p;
+    // the char in the glyphs
+    pub fn glyph(&self, glyphs: &dyn DotMatrix) -> char {
+        glyphs.glyph(self.code)
     }
 
-    fn toggle(&mut self, x: T, y: T) {
-        let p = get_pixel(x, y);
-        if self.code & p != 0 {
-            self.unset(x, y);
-        } else {
-            self.set(x, y);
-        }
+    // the bit is from the dot_bit
+    pub fn set(&mut self, bit: u32) {
+        self.code |= bit;
+    }
+
+    pub fn unset(&mut self, bit: u32) {
+        self.code &= !bit;
+    }
+
+    pub fn toggle(&mut self, bit: u32) {
+        self.code ^= bit;
     }
 }
 
@@ -73,17 +201,22 @@ impl fmt::Display for Pixel {
     }
 }
 
-fn get_pixel<T>(x: T, y: T) -> u32
+// the two put the dots into the same bits of the same cells
+pub(crate) fn same_dots(a: &dyn DotMatrix, b: &dyn DotMatrix) -> bool {
+    let (w, h) = a.size();
+    (w, h) == b.size() && (0..h).all(|row| (0..w).all(|col| a.bit(col, row) == b.bit(col, row)))
+}
+
+// the bit of the dot (x, y) in its cell
+pub(crate) fn dot_bit<T>(glyphs: &dyn DotMatrix, x: T, y: T) -> u32
 where
     T: Into<f64>,
 {
+    let (w, h) = glyphs.size();
     let (x, y) = (round(x), round(y));
-    let y = if y >= 0 {
-        [3, 2, 1, 0][(y % 4) as usize]
-    } else {
-        [3, 0, 1, 2][(y % 4).unsigned_abs() as usize]
-    };
-    PIXEL_MAP[y as usize][(x % 2).unsigned_abs() as usize]
+    // the y is facing up, but the row 0 is the top of the cell
+    let row = h as i32 - 1 - y.rem_euclid(h as i32);
+    glyphs.bit(x.rem_euclid(w as i32) as u8, row as u8)
 }
 
 // it's safety, dw :)
@@ -2,19 +2,37 @@
 // there isn't a good way to debug
 
 use std::io::Write;
-use std::{cmp, collections::HashMap};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
-use crossterm::{cursor::MoveToNextLine, queue, style::Print};
+use crossterm::{
+    cursor::{MoveTo, MoveToNextLine},
+    event::{KeyCode, KeyEvent},
+    queue,
+    style::Print,
+};
 
-use crate::braille;
 use crate::utils::get_pos;
 use crate::{
-    braille::PixelOp,
-    term::is_raw_mode,
-    utils::{round, RsilleErr},
+    braille::{dot_bit, same_dots, DotMatrix, Glyphs, Pixel},
+    term::{get_terminal_size, is_raw_mode},
+    utils::{char_width, check_zoom, round, RsilleErr},
 };
 
-use crate::color::{Color, Colored, ColoredChar};
+use crate::color::{Color, Colored, ColoredChar, TextStyle};
+use crate::Sprite;
+
+// the right cell of a wide char, nothing is printed on it
+pub(crate) const WIDE_TAIL: char = '\0';
+
+// (x, y, color) of every dot, used when rasterizing the canvas
+pub(crate) type Dots = Vec<(usize, usize, Option<Color>)>;
+
+// (cell, bit, color) of the dots waiting to be set
+type Cells = Vec<((i32, i32), u32, Option<Color>)>;
 
 /// Implement this for painting on [`Canvas`](struct.Canvas.html)
 pub trait Paint: Send + 'static {
@@ -22,6 +40,21 @@ pub trait Paint: Send + 'static {
     fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
     where
         T: Into<f64>;
+
+    /// Paint the object on all the locations, stop at the first error
+    ///
+    /// The dots are collected and set cell by cell at the end, like [`set_all`](struct.Canvas.html#method.set_all)
+    fn paint_iter<I>(&self, canvas: &mut Canvas, positions: I) -> Result<(), RsilleErr>
+    where
+        I: IntoIterator<Item = (f64, f64)>,
+    {
+        canvas.batch(|canvas| {
+            for (x, y) in positions {
+                self.paint(canvas, x, y)?;
+            }
+            Ok(())
+        })
+    }
 }
 
 // this is just for err: "Box<T> not impl Paint" xd
@@ -61,12 +94,16 @@ impl<T: Paint + ?Sized> Paint for Box<T> {
 
 #[derive(Debug, Clone)]
 pub struct Canvas {
-    minx: f64,                              // <= 0
-    miny: f64,                              // <= 0
-    width: i32,                             // >= 0
-    height: i32,                            // >= 0
-    pixels: HashMap<(i32, i32), Colored>,   // (col, row) -> colored
-    text: HashMap<(i32, i32), ColoredChar>, // (col, row) -> colored char
+    pub(crate) minx: f64,                              // <= 0
+    pub(crate) miny: f64,                              // <= 0
+    pub(crate) width: i32,                             // >= 0
+    pub(crate) height: i32,                            // >= 0
+    pub(crate) pixels: HashMap<(i32, i32), Colored>,   // (col, row) -> colored
+    pub(crate) text: HashMap<(i32, i32), ColoredChar>, // (col, row) -> colored char
+    pub(crate) glyphs: Arc<dyn DotMatrix>,
+    viewport: Option<(f64, f64, f64)>, // (x, y, zoom) of the center
+    dirty: Option<HashSet<(i32, i32)>>, // the changed cells, None if not tracking
+    batch: Option<Cells>,              // the dots set while batching, None if not batching
 }
 
 impl Canvas {
@@ -85,6 +122,10 @@ impl Canvas {
             height,
             pixels,
             text,
+            glyphs: Arc::new(Glyphs::Braille),
+            viewport: None,
+            dirty: None,
+            batch: None,
         }
     }
 
@@ -99,6 +140,40 @@ impl Canvas {
         Ok(())
     }
 
+    /// Paint all the objects on the location (x, y), stop at the first error
+    ///
+    /// The dots of all the objects are collected and set cell by cell at the end,
+    /// like [`set_all`](struct.Canvas.html#method.set_all), so it's faster than painting them one by one.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{extra::Turtle, Canvas};
+    /// let mut turtles = Vec::new();
+    /// for i in 0..12 {
+    ///     let mut t = Turtle::new();
+    ///     t.right(i as f64 * 30.0);
+    ///     t.forward(50.0);
+    ///     turtles.push(t);
+    /// }
+    /// let mut c = Canvas::new();
+    /// c.paint_all(&turtles, 0, 0).unwrap();
+    /// c.print();
+    /// ```
+    pub fn paint_all<T, N>(&mut self, targets: &[T], x: N, y: N) -> Result<(), RsilleErr>
+    where
+        T: Paint,
+        N: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        self.batch(|canvas| {
+            for target in targets {
+                target.paint(canvas, x, y)?;
+            }
+            Ok(())
+        })
+    }
+
     /// Print the canvas to the terminal
     ///
     /// If you want to print the canvas to a buffer, use the [`print_on`](struct.Canvas.html#method.print_on)
@@ -115,25 +190,52 @@ impl Canvas {
     where
         W: Write,
     {
-        self.print_impl(w, is_raw).map_err(RsilleErr::to_rsille_err)
+        self.print_impl(w, is_raw, None).map_err(RsilleErr::from)
+    }
+
+    /// Print the canvas to the buffer with the left top corner on *(col, row)* of the terminal
+    ///
+    /// Only the cells of the canvas are changed, the rest of the screen is kept.
+    /// The *(0, 0)* is the left top corner of the terminal.
+    pub fn print_at<W>(&self, w: &mut W, col: u16, row: u16) -> Result<(), RsilleErr>
+    where
+        W: Write,
+    {
+        self.print_impl(w, false, Some((col, row)))
+            .map_err(RsilleErr::from)
+    }
+
+    // the count of columns to print
+    pub(crate) fn cols(&self) -> u16 {
+        let (start_col, _) = self.cell_of(self.minx, self.miny);
+        (self.width - start_col).clamp(0, u16::MAX as i32) as u16
     }
 
-    fn print_impl<W>(&self, w: &mut W, is_raw: bool) -> std::io::Result<()>
+    // the count of rows to print
+    pub(crate) fn rows(&self) -> u16 {
+        let (_, start_row) = self.cell_of(self.minx, self.miny);
+        (self.height - start_row).clamp(0, u16::MAX as i32) as u16
+    }
+
+    fn print_impl<W>(
+        &self,
+        w: &mut W,
+        is_raw: bool,
+        origin: Option<(u16, u16)>,
+    ) -> std::io::Result<()>
     where
         W: Write,
     {
-        let (start_col, start_row) = get_pos(self.minx, self.miny);
-        for row in (start_row..self.height).rev() {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        for (i, row) in (start_row..self.height).rev().enumerate() {
+            if let Some((col, top)) = origin {
+                queue!(w, MoveTo(col, top.saturating_add(i as u16)))?;
+            }
             for col in start_col..self.width {
-                if let Some(text) = self.text.get(&(col, row)) {
-                    text.queue(w)?;
-                    continue;
-                }
-                if let Some(pixel) = self.pixels.get(&(col, row)) {
-                    pixel.queue(w)?;
-                } else {
-                    queue!(w, Print(braille::SPACE))?;
-                }
+                self.queue_cell(w, col, row)?;
+            }
+            if origin.is_some() {
+                continue;
             }
             if is_raw {
                 queue!(w, MoveToNextLine(1))?;
@@ -145,12 +247,195 @@ impl Canvas {
         Ok(())
     }
 
+    // print the cell, nothing for the tail of a wide char
+    pub(crate) fn queue_cell<W>(&self, w: &mut W, col: i32, row: i32) -> std::io::Result<()>
+    where
+        W: Write,
+    {
+        if let Some(text) = self.text.get(&(col, row)) {
+            if text.char() != WIDE_TAIL {
+                text.queue(w)?;
+            }
+        } else if let Some(pixel) = self.pixels.get(&(col, row)) {
+            pixel.queue(w, &*self.glyphs)?;
+        } else {
+            queue!(w, Print(self.glyphs.space()))?;
+        }
+        Ok(())
+    }
+
+    /// Set the chars used to show the dots, the default is [`Glyphs::Braille`](enum.Glyphs.html)
+    ///
+    /// It can be any [`DotMatrix`](trait.DotMatrix.html), like your own chars.
+    /// The dots are put into the cells by the [size](trait.DotMatrix.html#tymethod.size) of the glyphs,
+    /// so they're drawn at the native resolution of the glyphs.
+    /// It can be changed at any time, the dots already on the canvas stay where they are,
+    /// but the drawing looks bigger or smaller on the terminal.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{Canvas, Glyphs};
+    /// let mut c = Canvas::new();
+    /// for x in 0..40 {
+    ///     c.set(x, x / 2);
+    /// }
+    /// c.set_glyphs(Glyphs::Ascii);
+    /// c.print();
+    /// ```
+    pub fn set_glyphs<G>(&mut self, glyphs: G)
+    where
+        G: DotMatrix + 'static,
+    {
+        self.use_glyphs(Arc::new(glyphs));
+    }
+
+    /// Return the chars used to show the dots
+    ///
+    /// Give a look at the [`DotMatrix`](trait.DotMatrix.html) for the dots in a cell
+    pub fn glyphs(&self) -> &dyn DotMatrix {
+        &*self.glyphs
+    }
+
+    pub(crate) fn use_glyphs(&mut self, glyphs: Arc<dyn DotMatrix>) {
+        let from = std::mem::replace(&mut self.glyphs, glyphs);
+        if !same_dots(&*from, &*self.glyphs) {
+            self.relayout(&*from);
+        }
+    }
+
+    // put the dots and the text in the cells of the `from` glyphs into the cells of the now glyphs
+    fn relayout(&mut self, from: &dyn DotMatrix) {
+        self.flush();
+        self.touch_all();
+        let (w, h) = from.size();
+        let (w, h) = (w as i32, h as i32);
+        // the last dot of the last cell
+        let (width, height) = self.cell_of(self.width * w - 1, self.height * h - 1);
+        self.width = width + 1;
+        self.height = height + 1;
+        let pixels = std::mem::take(&mut self.pixels);
+        for ((col, row), pixel) in pixels {
+            let code = pixel.pixel().code();
+            for dy in 0..h {
+                for dx in 0..w {
+                    if code & from.bit(dx as u8, dy as u8) == 0 {
+                        continue;
+                    }
+                    let (x, y) = (col * w + dx, row * h + h - 1 - dy);
+                    let cell = self.cell_of(x, y);
+                    let bit = self.bit(x as f64, y as f64);
+                    self.pixels
+                        .entry(cell)
+                        .or_insert_with(|| {
+                            Colored::from_parts(
+                                Pixel::new(),
+                                pixel.foreground(),
+                                pixel.background(),
+                            )
+                        })
+                        .set(bit);
+                }
+            }
+        }
+        let text = std::mem::take(&mut self.text);
+        for ((col, row), c) in text {
+            // the tails are put with their wide chars
+            if c.char() != WIDE_TAIL {
+                let (col, row) = self.cell_of(col * w, row * h);
+                self.put_char(col, row, c);
+            }
+        }
+        self.touch_all();
+    }
+
+    /// Show the drawing through the viewport
+    ///
+    /// The dot *(x, y)* of the drawing is shown at the center of the canvas,
+    /// and the drawing is zoomed in `zoom` times, so the drawing can be much larger than the canvas.
+    /// All the things painted later are moved and zoomed,
+    /// and the dots out of the canvas are dropped instead of making the canvas larger,
+    /// so [`set_size`](struct.Canvas.html#method.set_size) first.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::Canvas;
+    /// let mut c = Canvas::new();
+    /// c.set_size(40, 16);
+    /// // look at the (100, 100) and zoom in 2 times
+    /// c.set_viewport(100, 100, 2);
+    /// c.line((90, 100), (110, 100));
+    /// c.set(0, 0); // it's dropped
+    /// c.print();
+    /// ```
+    pub fn set_viewport<T>(&mut self, x: T, y: T, zoom: T)
+    where
+        T: Into<f64>,
+    {
+        let zoom = zoom.into();
+        check_zoom(zoom);
+        self.viewport = Some((x.into(), y.into(), zoom));
+    }
+
+    /// Return the *(x, y, zoom)* of the viewport, `None` if there isn't a viewport
+    pub fn viewport(&self) -> Option<(f64, f64, f64)> {
+        self.viewport
+    }
+
+    /// Remove the viewport, the things will be painted on where they are
+    pub fn reset_viewport(&mut self) {
+        self.viewport = None;
+    }
+
+    /// Move the viewport by the dots on the canvas, not the dots of the drawing
+    ///
+    /// * `dx` - move right, negative for left
+    /// * `dy` - move up, negative for down
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        let (x, y, zoom) = self.viewport.unwrap_or((0.0, 0.0, 1.0));
+        self.viewport = Some((x + dx / zoom, y + dy / zoom, zoom));
+    }
+
+    /// Zoom the viewport at the center, bigger than 1 is zoom in
+    pub fn zoom(&mut self, factor: f64) {
+        check_zoom(factor);
+        let (x, y, zoom) = self.viewport.unwrap_or((0.0, 0.0, 1.0));
+        self.viewport = Some((x, y, zoom * factor));
+    }
+
+    /// Control the viewport by the key
+    ///
+    /// * `←` `→` `↑` `↓` - pan
+    /// * `+` `-` - zoom
+    /// * `0` - remove the viewport
+    ///
+    /// Return `true` if the key is used, paint again after it
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Left => self.pan(-4.0, 0.0),
+            KeyCode::Right => self.pan(4.0, 0.0),
+            KeyCode::Up => self.pan(0.0, 4.0),
+            KeyCode::Down => self.pan(0.0, -4.0),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom(1.1),
+            KeyCode::Char('-') => self.zoom(1.0 / 1.1),
+            KeyCode::Char('0') => self.reset_viewport(),
+            _ => return false,
+        }
+        true
+    }
+
     /// Clear the canvas
     ///
-    /// This method only clear those dots on the canvas, the size of the canvas will not change
+    /// This method only clear those dots and text on the canvas, the size of the canvas will not change
     /// If you want to clear the size too, use the [`reset`](struct.Canvas.html#method.reset)
     pub fn clear(&mut self) {
+        self.touch_all();
         self.pixels = HashMap::new();
+        self.text = HashMap::new();
+        if let Some(batch) = &mut self.batch {
+            batch.clear();
+        }
     }
 
     /// Reset the canvas to a new empty canvas
@@ -159,7 +444,12 @@ impl Canvas {
         self.miny = 0.0;
         self.width = 0;
         self.height = 0;
+        self.touch_all();
         self.pixels = HashMap::new();
+        self.text = HashMap::new();
+        if let Some(batch) = &mut self.batch {
+            batch.clear();
+        }
     }
 
     /// Set the size of the canvas
@@ -174,8 +464,8 @@ impl Canvas {
         T: Into<f64>,
     {
         // start_col, start_row < 0
-        let (max_col, max_row) = get_pos(width.into(), height.into());
-        let (start_col, start_row) = get_pos(self.minx, self.miny);
+        let (max_col, max_row) = self.cell_of(width.into(), height.into());
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
         if max_col > self.width - start_col {
             self.width = max_col + start_col;
         }
@@ -205,12 +495,74 @@ impl Canvas {
         T: Into<f64> + Copy,
     {
         let maxy = maxy.into();
-        let (_, max_row) = get_pos(0.0, maxy);
+        let (_, max_row) = self.cell_of(0.0, maxy);
         if max_row > self.height {
             self.height = max_row;
         }
     }
 
+    /// Map the position on the terminal to the dot on the canvas
+    ///
+    /// * `origin` - the *(column, row)* of the terminal where the canvas is printed, it's the left top corner
+    /// * `pos` - the *(column, row)* on the terminal, like the mouse event
+    ///
+    /// Both are 0-based like the mouse event of `crossterm`.
+    /// A cell of the terminal has many dots like 2 * 4 of the braille, so it returns the center of the cell,
+    /// just [`set`](struct.Canvas.html#method.set) it and the nearest dot will be set.
+    /// Return `None` if the position isn't on the canvas.
+    ///
+    /// The size of the canvas is only known after painting, use [`set_size`](struct.Canvas.html#method.set_size) for an empty canvas.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::Canvas;
+    /// let mut c = Canvas::new();
+    /// c.set_size(20, 8);
+    /// // the canvas is printed at the column 5 and row 2 of the terminal
+    /// let (x, y) = c.dot_at((5, 2), (6, 3)).unwrap();
+    /// assert_eq!((x, y), (2.5, 1.5));
+    /// assert_eq!(c.cell_at((x, y), (5, 2)), Some((6, 3)));
+    /// assert_eq!(c.dot_at((5, 2), (4, 3)), None);
+    /// ```
+    pub fn dot_at(&self, origin: (u16, u16), pos: (u16, u16)) -> Option<(f64, f64)> {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        if pos.0 < origin.0 || pos.1 < origin.1 {
+            return None;
+        }
+        let col = start_col + (pos.0 - origin.0) as i32;
+        // the first printed row is the top
+        let row = self.height - 1 - (pos.1 - origin.1) as i32;
+        if col >= self.width || row < start_row {
+            return None;
+        }
+        let (w, h) = self.glyphs.size();
+        let (w, h) = (w as f64, h as f64);
+        Some(self.unview(
+            col as f64 * w + (w - 1.0) / 2.0,
+            row as f64 * h + (h - 1.0) / 2.0,
+        ))
+    }
+
+    /// Map the dot on the canvas to the *(column, row)* on the terminal
+    ///
+    /// It's the opposite of [`dot_at`](struct.Canvas.html#method.dot_at), return `None` if the dot isn't on the canvas.
+    /// Both of them use the dots of the drawing when there is a viewport.
+    pub fn cell_at<T>(&self, xy: (T, T), origin: (u16, u16)) -> Option<(u16, u16)>
+    where
+        T: Into<f64>,
+    {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        let (x, y) = self.view(xy.0, xy.1);
+        let (col, row) = self.cell_of(x, y);
+        if col < start_col || col >= self.width || row < start_row || row >= self.height {
+            return None;
+        }
+        let c = origin.0 as i32 + col - start_col;
+        let r = origin.1 as i32 + self.height - 1 - row;
+        Some((u16::try_from(c).ok()?, u16::try_from(r).ok()?))
+    }
+
     /// Draw a dot on (x, y)
     ///
     /// Just use the (x, y) in your object, the algorithm will find the right location
@@ -231,6 +583,69 @@ impl Canvas {
         self.set_at(x, y, Some(color));
     }
 
+    /// Draw all the dots with the same color, `None` for no color
+    ///
+    /// It's much faster than [`set`](struct.Canvas.html#method.set) one by one for lots of dots,
+    /// like the big scatter or the particles, the dots in the same cell are set together.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{color::Color, Canvas};
+    /// let mut c = Canvas::new();
+    /// let dots = (0..10000).map(|i| {
+    ///     let t = i as f64 / 100.0;
+    ///     (t * t.cos(), t * t.sin())
+    /// });
+    /// c.set_all(dots, Some(Color::Green));
+    /// c.print();
+    /// ```
+    pub fn set_all<I, T>(&mut self, dots: I, color: Option<Color>)
+    where
+        I: IntoIterator<Item = (T, T)>,
+        T: Into<f64>,
+    {
+        self.flush();
+        let mut cells = Vec::new();
+        for (x, y) in dots {
+            let (x, y) = self.view(x, y);
+            if self.is_shown(x, y) {
+                cells.push((self.get_pos(x, y), self.bit(x, y), color));
+            }
+        }
+        self.set_cells(cells);
+    }
+
+    /// Erase the dot on (x, y)
+    pub fn unset<T>(&mut self, x: T, y: T)
+    where
+        T: Into<f64> + Copy,
+    {
+        self.flush();
+        let (x, y) = self.view(x, y);
+        if !self.is_shown(x, y) {
+            return;
+        }
+        let (col, row) = self.get_pos(x, y);
+        let bit = self.bit(x, y);
+        if let Some(pixel) = self.pixels.get_mut(&(col, row)) {
+            pixel.unset(bit);
+            self.touch(col, row);
+        }
+    }
+
+    /// Stamp the [`Sprite`](struct.Sprite.html) on the location (x, y)
+    ///
+    /// The (x, y) is the left bottom corner of the sprite.
+    /// Take a look at [`Sprite`](struct.Sprite.html) for the transparency, flipping and rotation.
+    pub fn blit<T>(&mut self, sprite: &Sprite, x: T, y: T)
+    where
+        T: Into<f64>,
+    {
+        // painting a sprite never fails, the dots are stamped cell by cell
+        let _ = sprite.paint(self, x, y);
+    }
+
     /// If the (x, y) is already set, then unset it
     ///
     /// If the (x, y) is unset, then set it
@@ -248,8 +663,10 @@ impl Canvas {
     where
         T: Into<f64>,
     {
-        let (x1, y1) = (round(xy1.0), round(xy1.1));
-        let (x2, y2) = (round(xy2.0), round(xy2.1));
+        let (x1, y1) = self.view(xy1.0, xy1.1);
+        let (x2, y2) = self.view(xy2.0, xy2.1);
+        let (x1, y1) = (round(x1), round(y1));
+        let (x2, y2) = (round(x2), round(y2));
         let d = |v1, v2| {
             if v1 <= v2 {
                 (v2 - v1, 1.0)
@@ -260,7 +677,8 @@ impl Canvas {
 
         let (xdiff, xdir) = d(x1, x2);
         let (ydiff, ydif) = d(y1, y2);
-        let r = cmp::max(xdiff, ydiff);
+        // at least one step, or the same start and end is 0 / 0
+        let r = cmp::max(xdiff, ydiff).max(1);
 
         for i in 0..=r {
             let r = r as f64;
@@ -268,7 +686,7 @@ impl Canvas {
             let (xd, yd) = (xdiff as f64, ydiff as f64);
             let x = x1 as f64 + i * xd / r * xdir;
             let y = y1 as f64 + i * yd / r * ydif;
-            self.set(x, y);
+            self.set_dot(x, y, None);
         }
     }
 
@@ -285,8 +703,10 @@ impl Canvas {
     where
         T: Into<f64>,
     {
-        let (x1, y1) = (round(xy1.0), round(xy1.1));
-        let (x2, y2) = (round(xy2.0), round(xy2.1));
+        let (x1, y1) = self.view(xy1.0, xy1.1);
+        let (x2, y2) = self.view(xy2.0, xy2.1);
+        let (x1, y1) = (round(x1), round(y1));
+        let (x2, y2) = (round(x2), round(y2));
         let d = |v1, v2| {
             if v1 <= v2 {
                 (v2 - v1, 1.0)
@@ -297,7 +717,8 @@ impl Canvas {
 
         let (xdiff, xdir) = d(x1, x2);
         let (ydiff, ydif) = d(y1, y2);
-        let r = cmp::max(xdiff, ydiff);
+        // at least one step, or the same start and end is 0 / 0
+        let r = cmp::max(xdiff, ydiff).max(1);
 
         for i in 0..=r {
             let r = r as f64;
@@ -305,7 +726,7 @@ impl Canvas {
             let (xd, yd) = (xdiff as f64, ydiff as f64);
             let x = x1 as f64 + i * xd / r * xdir;
             let y = y1 as f64 + i * yd / r * ydif;
-            self.put(x, y, c, color);
+            self.put_dot(x, y, c, color);
         }
     }
 
@@ -316,8 +737,10 @@ impl Canvas {
     where
         T: Into<f64> + Copy,
     {
-        let (x1, y1) = (round(xy1.0), round(xy1.1));
-        let (x2, y2) = (round(xy2.0), round(xy2.1));
+        let (x1, y1) = self.view(xy1.0, xy1.1);
+        let (x2, y2) = self.view(xy2.0, xy2.1);
+        let (x1, y1) = (round(x1), round(y1));
+        let (x2, y2) = (round(x2), round(y2));
         let d = |v1, v2| {
             if v1 <= v2 {
                 (v2 - v1, 1.0)
@@ -328,7 +751,8 @@ impl Canvas {
 
         let (xdiff, xdir) = d(x1, x2);
         let (ydiff, ydif) = d(y1, y2);
-        let r = cmp::max(xdiff, ydiff);
+        // at least one step, or the same start and end is 0 / 0
+        let r = cmp::max(xdiff, ydiff).max(1);
 
         for i in 0..=r {
             let r = r as f64;
@@ -336,30 +760,52 @@ impl Canvas {
             let (xd, yd) = (xdiff as f64, ydiff as f64);
             let x = x1 as f64 + i * xd / r * xdir;
             let y = y1 as f64 + i * yd / r * ydif;
-            self.set_colorful(x, y, color);
+            self.set_dot(x, y, Some(color));
         }
     }
 
     /// Put text on canvas
     ///
-    /// It can draw any character on canvas,
+    /// * `x`, `y` - the location of the first character, in dots like [`set`](struct.Canvas.html#method.set)
+    /// * `text` - the text, every character takes one cell of the terminal,
+    ///   but the wide characters like CJK take two cells and the combining marks are ignored
+    /// * `style` - the [`TextStyle`](color/struct.TextStyle.html), `None`, `Some(color)` or a color also works
+    ///
+    /// Those cells covered by the text are reserved for the text,
     /// and when there are both a braille code and any char on *(x, y)*,
     /// the character will cover the braille code!
-    pub fn put_text<T>(&mut self, x: T, y: T, text: &str, color: Option<Color>)
+    /// The braille code in the other cells won't be changed,
+    /// so it's easy to put labels, legends or anything like that beside your braille art.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{color::{Color, TextStyle}, Canvas};
+    /// let mut c = Canvas::new();
+    /// for x in 0..100 {
+    ///     c.set(x, 0);
+    /// }
+    /// c.put_text(0, 8, "y = 0", TextStyle::new().fg(Color::Green).bold());
+    /// c.print();
+    /// ```
+    pub fn put_text<T, S>(&mut self, x: T, y: T, text: &str, style: S)
     where
         T: Into<f64>,
+        S: Into<TextStyle>,
     {
+        let style = style.into();
+        let (x, y) = self.view(x, y);
+        if !self.is_shown(x, y) {
+            return;
+        }
         let (col, row) = self.get_pos(x, y);
-        if let Some(color) = color {
-            for (i, c) in text.chars().enumerate() {
-                let mut c = ColoredChar::new(c);
-                c.set_foregound_color(color);
-                self.text.insert((col + i as i32, row), c);
-            }
-        } else {
-            for (i, c) in text.chars().enumerate() {
-                self.text.insert((col + i as i32, row), ColoredChar::new(c));
-            }
+        let mut len = 0;
+        for c in text.chars() {
+            len += self.put_char(col + len, row, ColoredChar::with_style(c, style));
+        }
+        // make sure the whole text is inside the canvas
+        if col + len > self.width {
+            self.width = col + len;
         }
     }
 
@@ -372,6 +818,15 @@ impl Canvas {
     where
         T: Into<f64>,
     {
+        let (x, y) = self.view(x, y);
+        self.put_dot(x, y, c, color);
+    }
+
+    // put the char on the dot in the canvas, the viewport is already used
+    fn put_dot(&mut self, x: f64, y: f64, c: char, color: Option<Color>) {
+        if !self.is_shown(x, y) {
+            return;
+        }
         let (col, row) = self.get_pos(x, y);
         let c = if let Some(color) = color {
             let mut c = ColoredChar::new(c);
@@ -380,20 +835,168 @@ impl Canvas {
         } else {
             ColoredChar::new(c)
         };
+        self.put_char(col, row, c);
+    }
+
+    // put the char on the cell and return the cells it takes,
+    // the wide char takes this cell and the right one
+    fn put_char(&mut self, col: i32, row: i32, c: ColoredChar) -> i32 {
+        let w = char_width(c.char()) as i32;
+        if w == 0 {
+            return 0;
+        }
+        // never leave the half of a wide char
+        for i in 0..w {
+            self.remove_char(col + i, row);
+        }
         self.text.insert((col, row), c);
+        self.touch(col, row);
+        if w == 2 {
+            self.text.insert(
+                (col + 1, row),
+                ColoredChar::with_style(WIDE_TAIL, c.style()),
+            );
+            self.touch(col + 1, row);
+        }
+        w
+    }
+
+    fn remove_char(&mut self, col: i32, row: i32) {
+        match self.text.remove(&(col, row)) {
+            Some(c) if c.char() == WIDE_TAIL => {
+                self.text.remove(&(col - 1, row));
+                self.touch(col - 1, row);
+            }
+            Some(c) if char_width(c.char()) == 2 => {
+                self.text.remove(&(col + 1, row));
+                self.touch(col + 1, row);
+            }
+            _ => {}
+        }
+        self.touch(col, row);
+    }
+
+    // track the changed cells or not
+    pub(crate) fn track_changes(&mut self, track: bool) {
+        self.dirty = if track { Some(HashSet::new()) } else { None };
+    }
+
+    // return the changed cells since the last time
+    pub(crate) fn take_changes(&mut self) -> HashSet<(i32, i32)> {
+        self.dirty.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    fn touch(&mut self, col: i32, row: i32) {
+        if let Some(dirty) = &mut self.dirty {
+            dirty.insert((col, row));
+        }
+    }
+
+    fn touch_all(&mut self) {
+        if let Some(dirty) = &mut self.dirty {
+            dirty.extend(self.pixels.keys().chain(self.text.keys()));
+        }
+    }
+
+    // put the other canvas on self, the text of other will cover the text of self
+    pub(crate) fn merge(&mut self, other: &Canvas) {
+        if !same_dots(&*other.glyphs, &*self.glyphs) {
+            let mut other = other.clone();
+            other.use_glyphs(self.glyphs.clone());
+            return self.merge(&other);
+        }
+        self.flush();
+        self.minx = self.minx.min(other.minx);
+        self.miny = self.miny.min(other.miny);
+        self.width = self.width.max(other.width);
+        self.height = self.height.max(other.height);
+        for (pos, pixel) in &other.pixels {
+            self.touch(pos.0, pos.1);
+            self.pixels
+                .entry(*pos)
+                .and_modify(|p| p.merge(pixel))
+                .or_insert(*pixel);
+        }
+        // the tails are put with their wide chars
+        for (&(col, row), c) in &other.text {
+            if c.char() != WIDE_TAIL {
+                self.put_char(col, row, *c);
+            }
+        }
+    }
+
+    // the size in dots and all those dots, the (0, 0) is the left top corner
+    #[allow(unused)]
+    pub(crate) fn raster(&self) -> (usize, usize, Dots) {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        let (w, h) = self.glyphs.size();
+        let width = (self.width - start_col).max(0) as usize * w as usize;
+        let height = (self.height - start_row).max(0) as usize * h as usize;
+        let mut dots = Vec::new();
+        for (&(col, row), pixel) in &self.pixels {
+            if col < start_col || row < start_row || col >= self.width || row >= self.height {
+                continue;
+            }
+            let (x, y) = (
+                (col - start_col) as usize * w as usize,
+                (self.height - 1 - row) as usize * h as usize,
+            );
+            let code = pixel.pixel().code();
+            for dy in 0..h {
+                for dx in 0..w {
+                    if code & self.glyphs.bit(dx, dy) != 0 {
+                        dots.push((x + dx as usize, y + dy as usize, pixel.foreground()));
+                    }
+                }
+            }
+        }
+        (width, height, dots)
+    }
+
+    // all the text in (col, row), the (0, 0) is the left top corner
+    #[allow(unused)]
+    pub(crate) fn raster_text(&self) -> Vec<(usize, usize, char, Option<Color>)> {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        let mut text = Vec::new();
+        for (&(col, row), c) in &self.text {
+            if c.char() == WIDE_TAIL {
+                continue;
+            }
+            if col < start_col || row < start_row || col >= self.width || row >= self.height {
+                continue;
+            }
+            let (col, row) = ((col - start_col) as usize, (self.height - 1 - row) as usize);
+            text.push((col, row, c.char(), c.foreground()));
+        }
+        text
     }
 
     fn set_at<T>(&mut self, x: T, y: T, color: Option<Color>)
     where
         T: Into<f64> + Copy,
     {
+        let (x, y) = self.view(x, y);
+        self.set_dot(x, y, color);
+    }
+
+    // set the dot in the canvas, the viewport is already used
+    fn set_dot(&mut self, x: f64, y: f64, color: Option<Color>) {
+        if !self.is_shown(x, y) {
+            return;
+        }
         let (col, row) = self.get_pos(x, y);
+        let bit = self.bit(x, y);
+        if let Some(batch) = &mut self.batch {
+            batch.push(((col, row), bit, color));
+            return;
+        }
         if let Some(pixel) = self.pixels.get_mut(&(col, row)) {
-            pixel.set(x, y);
+            pixel.set(bit);
         } else {
             self.pixels.insert((col, row), Colored::new());
-            self.pixels.get_mut(&(col, row)).unwrap().set(x, y);
+            self.pixels.get_mut(&(col, row)).unwrap().set(bit);
         }
+        self.touch(col, row);
         if let Some(color) = color {
             self.pixels
                 .get_mut(&(col, row))
@@ -406,15 +1009,177 @@ impl Canvas {
     where
         T: Into<f64> + Copy,
     {
+        self.flush();
+        let (x, y) = self.view(x, y);
+        if !self.is_shown(x, y) {
+            return;
+        }
         let (col, row) = self.get_pos(x, y);
+        let bit = self.bit(x, y);
         if let Some(pixel) = self.pixels.get_mut(&(col, row)) {
-            pixel.toggle(x, y);
+            pixel.toggle(bit);
         } else {
             self.pixels.insert((col, row), Colored::new());
-            self.pixels.get_mut(&(col, row)).unwrap().toggle(x, y);
+            self.pixels.get_mut(&(col, row)).unwrap().toggle(bit);
+        }
+        self.touch(col, row);
+    }
+
+    // set and erase the dots cell by cell, `None` erases the dot and `Some(Color::Reset)` sets it without color
+    pub(crate) fn stamp<I>(&mut self, dots: I)
+    where
+        I: IntoIterator<Item = (f64, f64, Option<Color>)>,
+    {
+        self.flush();
+        // (set, unset, color) of every cell, the later dot covers the former
+        let mut masks: HashMap<(i32, i32), (u32, u32, Option<Color>)> = HashMap::new();
+        for (x, y, dot) in dots {
+            let (x, y) = self.view(x, y);
+            if !self.is_shown(x, y) {
+                continue;
+            }
+            let (cell, bit) = (self.get_pos(x, y), self.bit(x, y));
+            let mask = masks.entry(cell).or_default();
+            match dot {
+                Some(color) => {
+                    mask.0 |= bit;
+                    mask.1 &= !bit;
+                    if color != Color::Reset {
+                        mask.2 = Some(color);
+                    }
+                }
+                None => {
+                    mask.0 &= !bit;
+                    mask.1 |= bit;
+                }
+            }
+        }
+        for ((col, row), (set, unset, color)) in masks {
+            let pixel = if set == 0 {
+                // erasing doesn't make the cell
+                let Some(pixel) = self.pixels.get_mut(&(col, row)) else {
+                    continue;
+                };
+                pixel
+            } else {
+                self.pixels.entry((col, row)).or_insert_with(Colored::new)
+            };
+            pixel.unset(unset);
+            pixel.set(set);
+            if let Some(color) = color {
+                pixel.set_foregound_color(color);
+            }
+            self.touch(col, row);
         }
     }
 
+    // collect the dots set in `f`, and set them cell by cell after it
+    fn batch<F>(&mut self, f: F) -> Result<(), RsilleErr>
+    where
+        F: FnOnce(&mut Self) -> Result<(), RsilleErr>,
+    {
+        // already batching by the outer one
+        if self.batch.is_some() {
+            return f(self);
+        }
+        self.batch = Some(Vec::new());
+        let result = f(self);
+        if let Some(cells) = self.batch.take() {
+            self.set_cells(cells);
+        }
+        result
+    }
+
+    // set the dots waiting in the batch, before anything can't wait
+    fn flush(&mut self) {
+        if let Some(cells) = self.batch.as_mut().map(std::mem::take) {
+            self.set_cells(cells);
+        }
+    }
+
+    // set the dots in the same cell together, the later color covers the former
+    fn set_cells(&mut self, mut cells: Cells) {
+        // one lookup for all the dots in a cell
+        cells.sort_by_key(|c| c.0);
+        let mut start = 0;
+        while start < cells.len() {
+            let (col, row) = cells[start].0;
+            let end = cells[start..]
+                .iter()
+                .position(|c| c.0 != (col, row))
+                .map_or(cells.len(), |n| start + n);
+            let pixel = self.pixels.entry((col, row)).or_insert_with(Colored::new);
+            for &(_, bit, color) in &cells[start..end] {
+                pixel.set(bit);
+                if let Some(color) = color {
+                    pixel.set_foregound_color(color);
+                }
+            }
+            start = end;
+            self.touch(col, row);
+        }
+    }
+
+    // the center of the canvas in dots
+    fn center(&self) -> (f64, f64) {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        let (w, h) = self.glyphs.size();
+        (
+            (start_col + self.width) as f64 * w as f64 / 2.0,
+            (start_row + self.height) as f64 * h as f64 / 2.0,
+        )
+    }
+
+    // the dot of the drawing to the dot in the canvas
+    fn view<T>(&self, x: T, y: T) -> (f64, f64)
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        let Some((vx, vy, zoom)) = self.viewport else {
+            return (x, y);
+        };
+        let (cx, cy) = self.center();
+        ((x - vx) * zoom + cx, (y - vy) * zoom + cy)
+    }
+
+    // the dot in the canvas to the dot of the drawing
+    fn unview(&self, x: f64, y: f64) -> (f64, f64) {
+        let Some((vx, vy, zoom)) = self.viewport else {
+            return (x, y);
+        };
+        let (cx, cy) = self.center();
+        ((x - cx) / zoom + vx, (y - cy) / zoom + vy)
+    }
+
+    // the (min x, min y, max x, max y) of the dots can be seen in the drawing,
+    // it's the canvas with the viewport, or the canvas and a terminal around the (0, 0) without it
+    pub(crate) fn shown_area(&self) -> (f64, f64, f64, f64) {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        let (w, h) = self.glyphs.size();
+        let (w, h) = (w as f64, h as f64);
+        let (x0, y0) = (start_col as f64 * w, start_row as f64 * h);
+        let (x1, y1) = (self.width as f64 * w, self.height as f64 * h);
+        if self.viewport.is_none() {
+            let (cols, rows) = get_terminal_size();
+            let (tw, th) = (cols as f64 * w, rows as f64 * h);
+            return (x0.min(-tw), y0.min(-th), x1.max(tw), y1.max(th));
+        }
+        let (ax, ay) = self.unview(x0, y0);
+        let (bx, by) = self.unview(x1, y1);
+        (ax.min(bx), ay.min(by), ax.max(bx), ay.max(by))
+    }
+
+    // the canvas doesn't grow when there is a viewport
+    fn is_shown(&self, x: f64, y: f64) -> bool {
+        if self.viewport.is_none() {
+            return true;
+        }
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        let (col, row) = self.cell_of(x, y);
+        col >= start_col && col < self.width && row >= start_row && row < self.height
+    }
+
     fn get_pos<T>(&mut self, x: T, y: T) -> (i32, i32)
     where
         T: Into<f64>,
@@ -426,7 +1191,7 @@ impl Canvas {
         if y < self.miny {
             self.miny = y;
         }
-        let (col, row) = get_pos(x, y);
+        let (col, row) = get_pos(x, y, self.glyphs.size());
         if row >= self.height {
             self.height = row.abs() + 1;
         }
@@ -435,4 +1200,17 @@ impl Canvas {
         }
         (col, row)
     }
+
+    // the (col, row) of the dot in the canvas
+    pub(crate) fn cell_of<T>(&self, x: T, y: T) -> (i32, i32)
+    where
+        T: Into<f64>,
+    {
+        get_pos(x, y, self.glyphs.size())
+    }
+
+    // the bit of the dot in its cell
+    fn bit(&self, x: f64, y: f64) -> u32 {
+        dot_bit(&*self.glyphs, x, y)
+    }
 }
@@ -18,14 +18,167 @@
 
 use std::io;
 
-use crate::braille::{Pixel, PixelOp};
+use crate::term;
+
+use crate::braille::{DotMatrix, Pixel};
 
 pub use crossterm::style::Color;
 use crossterm::{
     queue,
-    style::{Colors, Print, ResetColor, SetColors},
+    style::{
+        Attribute, Attributes, Colors, Print, ResetColor, SetAttribute, SetAttributes, SetColors,
+        SetUnderlineColor,
+    },
 };
 
+/// The style of text on the canvas
+///
+/// It's used by [`put_text`](../struct.Canvas.html#method.put_text).
+/// For compatibility, `None`, `Some(color)` and `color` can all be used as a style.
+///
+/// The double and curly underline and the underline color are only shown
+/// when [`term::styled_underline`](crate::term::styled_underline) is `true`,
+/// or it's the normal underline.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{color::{Color, TextStyle}, Canvas};
+/// let mut c = Canvas::new();
+/// let style = TextStyle::new().fg(Color::Red).bold();
+/// c.put_text(0, 0, "hello", style);
+/// // the typo of the spell checker
+/// let typo = TextStyle::new().curly_underline().underline_color(Color::Red);
+/// c.put_text(0, 4, "wrold", typo);
+/// c.put_text(0, 8, "removed", TextStyle::new().strikethrough());
+/// c.print();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextStyle {
+    pub(crate) foreground: Option<Color>,
+    pub(crate) background: Option<Color>,
+    pub(crate) bold: bool,
+    pub(crate) italic: bool,
+    pub(crate) underline: Option<Underline>,
+    pub(crate) underline_color: Option<Color>,
+    pub(crate) strikethrough: bool,
+    pub(crate) blink: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Underline {
+    Single,
+    Double,
+    Curly,
+}
+
+impl TextStyle {
+    /// Return a new style without any color or attribute
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the foreground color
+    pub fn fg(mut self, color: Color) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    /// Set the background color
+    pub fn bg(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Make the text bold
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Make the text italic
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Make the text underlined
+    pub fn underline(mut self) -> Self {
+        self.underline = Some(Underline::Single);
+        self
+    }
+
+    /// Make the text underlined twice
+    pub fn double_underline(mut self) -> Self {
+        self.underline = Some(Underline::Double);
+        self
+    }
+
+    /// Make the text underlined by the wavy line, like the typo in the editors
+    pub fn curly_underline(mut self) -> Self {
+        self.underline = Some(Underline::Curly);
+        self
+    }
+
+    /// Set the color of the underline, it's same as the text by default
+    ///
+    /// It doesn't underline the text, use it with the underlines
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.underline_color = Some(color);
+        self
+    }
+
+    /// Cross the text out
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Make the text blink slowly
+    pub fn blink(mut self) -> Self {
+        self.blink = true;
+        self
+    }
+
+    fn attributes(&self) -> Attributes {
+        let mut attrs = Attributes::default();
+        if self.bold {
+            attrs.set(Attribute::Bold);
+        }
+        if self.italic {
+            attrs.set(Attribute::Italic);
+        }
+        match self.underline {
+            Some(Underline::Single) => attrs.set(Attribute::Underlined),
+            Some(Underline::Double) => attrs.set(Attribute::DoubleUnderlined),
+            Some(Underline::Curly) => attrs.set(Attribute::Undercurled),
+            None => (),
+        }
+        if self.strikethrough {
+            attrs.set(Attribute::CrossedOut);
+        }
+        if self.blink {
+            attrs.set(Attribute::SlowBlink);
+        }
+        attrs
+    }
+}
+
+impl From<Color> for TextStyle {
+    fn from(color: Color) -> Self {
+        Self::new().fg(color)
+    }
+}
+
+impl From<Option<Color>> for TextStyle {
+    fn from(color: Option<Color>) -> Self {
+        Self {
+            foreground: color,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct Colored {
     pixel: Pixel,
@@ -54,6 +207,43 @@ impl Colored {
         }
     }
 
+    pub(crate) fn pixel(&self) -> Pixel {
+        self.pixel
+    }
+
+    pub(crate) fn foreground(&self) -> Option<Color> {
+        self.color.foreground
+    }
+
+    pub(crate) fn background(&self) -> Option<Color> {
+        self.color.background
+    }
+
+    // put the other on self, the dots are both kept and the color of other is used
+    pub(crate) fn merge(&mut self, other: &Colored) {
+        self.pixel = unsafe { Pixel::from_unchecked(self.pixel.code() | other.pixel.code()) };
+        if other.color.foreground.is_some() {
+            self.color.foreground = other.color.foreground;
+        }
+        if other.color.background.is_some() {
+            self.color.background = other.color.background;
+        }
+    }
+
+    pub(crate) fn from_parts(
+        pixel: Pixel,
+        foreground: Option<Color>,
+        background: Option<Color>,
+    ) -> Self {
+        Self {
+            pixel,
+            color: Colors {
+                foreground,
+                background,
+            },
+        }
+    }
+
     pub(crate) fn set_foregound_color(&mut self, color: Color) {
         self.color.foreground = Some(color);
     }
@@ -62,34 +252,32 @@ impl Colored {
         self.color.background = Some(color);
     }
 
-    pub(crate) fn queue(&self, buffer: &mut impl io::Write) -> io::Result<()> {
+    pub(crate) fn queue(
+        &self,
+        buffer: &mut impl io::Write,
+        glyphs: &dyn DotMatrix,
+    ) -> io::Result<()> {
+        let c = self.pixel.glyph(glyphs);
         if self.color.foreground.is_none() && self.color.background.is_none() {
-            queue!(buffer, Print(format!("{}", self.pixel)),)
+            queue!(buffer, Print(c),)
         } else {
-            queue!(
-                buffer,
-                SetColors(self.color),
-                Print(format!("{}", self.pixel)),
-                ResetColor
-            )
+            queue!(buffer, SetColors(self.color), Print(c), ResetColor)
         }
     }
 }
 
-impl<T> PixelOp<T> for Colored
-where
-    T: Into<f64> + Copy,
-{
-    fn set(&mut self, x: T, y: T) {
-        self.pixel.set(x, y);
+impl Colored {
+    // the bit of the dot in the cell, give a look at the DotMatrix
+    pub(crate) fn set(&mut self, bit: u32) {
+        self.pixel.set(bit);
     }
 
-    fn unset(&mut self, x: T, y: T) {
-        self.pixel.unset(x, y);
+    pub(crate) fn unset(&mut self, bit: u32) {
+        self.pixel.unset(bit);
     }
 
-    fn toggle(&mut self, x: T, y: T) {
-        self.pixel.toggle(x, y);
+    pub(crate) fn toggle(&mut self, bit: u32) {
+        self.pixel.toggle(bit);
     }
 }
 
@@ -97,6 +285,8 @@ where
 pub(crate) struct ColoredChar {
     c: char,
     color: Colors,
+    attrs: Attributes,
+    underline_color: Option<Color>,
 }
 
 #[allow(unused)]
@@ -108,6 +298,49 @@ impl ColoredChar {
                 foreground: None,
                 background: None,
             },
+            attrs: Attributes::default(),
+            underline_color: None,
+        }
+    }
+
+    pub(crate) fn with_style(c: char, style: TextStyle) -> Self {
+        Self {
+            c,
+            color: Colors {
+                foreground: style.foreground,
+                background: style.background,
+            },
+            attrs: style.attributes(),
+            underline_color: style.underline_color,
+        }
+    }
+
+    pub(crate) fn char(&self) -> char {
+        self.c
+    }
+
+    pub(crate) fn foreground(&self) -> Option<Color> {
+        self.color.foreground
+    }
+
+    pub(crate) fn style(&self) -> TextStyle {
+        TextStyle {
+            foreground: self.color.foreground,
+            background: self.color.background,
+            bold: self.attrs.has(Attribute::Bold),
+            italic: self.attrs.has(Attribute::Italic),
+            underline: if self.attrs.has(Attribute::DoubleUnderlined) {
+                Some(Underline::Double)
+            } else if self.attrs.has(Attribute::Undercurled) {
+                Some(Underline::Curly)
+            } else if self.attrs.has(Attribute::Underlined) {
+                Some(Underline::Single)
+            } else {
+                None
+            },
+            underline_color: self.underline_color,
+            strikethrough: self.attrs.has(Attribute::CrossedOut),
+            blink: self.attrs.has(Attribute::SlowBlink),
         }
     }
 
@@ -120,7 +353,28 @@ impl ColoredChar {
     }
 
     pub(crate) fn queue(&self, buffer: &mut impl io::Write) -> io::Result<()> {
-        if self.color.foreground.is_none() && self.color.background.is_none() {
+        if !self.attrs.is_empty() {
+            let mut attrs = self.attrs;
+            let styled = term::styled_underline();
+            // the terminal may show the unknown underlines as nothing or even the blink
+            if !styled
+                && (attrs.has(Attribute::DoubleUnderlined) || attrs.has(Attribute::Undercurled))
+            {
+                attrs.unset(Attribute::DoubleUnderlined);
+                attrs.unset(Attribute::Undercurled);
+                attrs.set(Attribute::Underlined);
+            }
+            queue!(buffer, SetColors(self.color), SetAttributes(attrs))?;
+            if let Some(color) = self.underline_color.filter(|_| styled) {
+                queue!(buffer, SetUnderlineColor(color))?;
+            }
+            queue!(
+                buffer,
+                Print(format!("{}", self.c)),
+                SetAttribute(Attribute::Reset),
+                ResetColor
+            )
+        } else if self.color.foreground.is_none() && self.color.background.is_none() {
             queue!(buffer, Print(format!("{}", self.c)),)
         } else {
             queue!(
@@ -132,3 +386,57 @@ impl ColoredChar {
         }
     }
 }
+
+// the rgb of those colors, the named colors are same as the xterm
+#[allow(unused)]
+pub(crate) fn to_rgb(color: Color) -> (u8, u8, u8) {
+    const NAMED: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    match color {
+        Color::Reset | Color::White => NAMED[15],
+        Color::Black => NAMED[0],
+        Color::DarkRed => NAMED[1],
+        Color::DarkGreen => NAMED[2],
+        Color::DarkYellow => NAMED[3],
+        Color::DarkBlue => NAMED[4],
+        Color::DarkMagenta => NAMED[5],
+        Color::DarkCyan => NAMED[6],
+        Color::Grey => NAMED[7],
+        Color::DarkGrey => NAMED[8],
+        Color::Red => NAMED[9],
+        Color::Green => NAMED[10],
+        Color::Yellow => NAMED[11],
+        Color::Blue => NAMED[12],
+        Color::Magenta => NAMED[13],
+        Color::Cyan => NAMED[14],
+        Color::Rgb { r, g, b } => (r, g, b),
+        Color::AnsiValue(v) => match v {
+            0..=15 => NAMED[v as usize],
+            16..=231 => {
+                let v = v - 16;
+                let level = |c: u8| if c == 0 { 0 } else { 55 + c * 40 };
+                (level(v / 36), level(v / 6 % 6), level(v % 6))
+            }
+            _ => {
+                let g = 8 + (v - 232) * 10;
+                (g, g, g)
+            }
+        },
+    }
+}
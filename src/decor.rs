@@ -1,9 +1,13 @@
-use crate::Canvas;
+use crate::{
+    color::ColoredChar,
+    utils::{ErrKind, RsilleErr},
+    Canvas,
+};
 
 /// The box outside the object
 ///
 /// It include many `char` for making a **box**.
-/// But it *not* include the functions for making box!
+/// Use [`draw`](struct.Decor.html#method.draw) to draw the box on the canvas.
 ///
 /// As shown in the figure below, the abbreviation is:  
 /// `l`: left, `r`: right, `t`: top, `b`: bottom, `e`: edge, `c`: cross
@@ -24,6 +28,7 @@ use crate::Canvas;
 ///
 /// In general, you don't need to manually set up your own.
 /// Some useful constructor methods can directly generate specific styles of borders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Decor {
     /// left top corner
     pub lt: char,
@@ -75,6 +80,109 @@ impl Decor {
     pub fn get_cross(&self) -> (char, char, char, char, char) {
         (self.tc, self.rc, self.bc, self.lc, self.cross)
     }
+
+    /// Make your own style from 15 chars
+    ///
+    /// The order is the same as the fields: `lt rt rb lb te re be le tc rc bc lc cross sh sv`,
+    /// and the whitespaces are ignored.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::Decor;
+    /// let ascii = Decor::custom("++++ -|-| ++++ + -|").unwrap();
+    /// assert_eq!(ascii.get_corner(), ('+', '+', '+', '+'));
+    /// assert!(Decor::custom("+-|").is_err());
+    /// ```
+    pub fn custom(chars: &str) -> Result<Self, RsilleErr> {
+        let c: Vec<char> = chars.chars().filter(|c| !c.is_whitespace()).collect();
+        if c.len() != 15 {
+            return Err(RsilleErr::with_kind(
+                ErrKind::Parse,
+                format!("decor needs 15 chars, but got {}", c.len()),
+            ));
+        }
+        Ok(Decor {
+            lt: c[0],
+            rt: c[1],
+            rb: c[2],
+            lb: c[3],
+            te: c[4],
+            re: c[5],
+            be: c[6],
+            le: c[7],
+            tc: c[8],
+            rc: c[9],
+            bc: c[10],
+            lc: c[11],
+            cross: c[12],
+            sh: c[13],
+            sv: c[14],
+        })
+    }
+
+    /// Draw the box on the canvas
+    ///
+    /// * `start` - the left bottom corner, in dots like [`set`](struct.Canvas.html#method.set)
+    /// * `end` - the right top corner
+    /// * `title` - the title in the top edge like `┤ title ├`, it's cut if the box is too small
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{Canvas, Decor};
+    /// let mut c = Canvas::new();
+    /// Decor::rounded().draw(&mut c, (0, 0), (30, 12), Some("hello"));
+    /// c.print();
+    /// ```
+    pub fn draw<T>(&self, canvas: &mut Canvas, start: (T, T), end: (T, T), title: Option<&str>)
+    where
+        T: Into<f64>,
+    {
+        let (start, end) = (
+            (start.0.into(), start.1.into()),
+            (end.0.into(), end.1.into()),
+        );
+        draw_box(canvas, start, end, self);
+        let Some(title) = title else {
+            return;
+        };
+        // the cells between the corners, 4 of them are for the crosses and spaces
+        let room = ((end.0 - start.0) / 2.0).round() as usize;
+        let room = room.saturating_sub(5);
+        if room == 0 {
+            return;
+        }
+        let title: String = title.chars().take(room).collect();
+        let text = format!("{} {} {}", self.rc, title, self.lc);
+        canvas.put_text(start.0 + 2.0, end.1, &text, None);
+    }
+
+    /// Merge two box-drawing chars into the junction of them
+    ///
+    /// The light, heavy and double lines all work, and the rounded and dashed lines are merged as the solid lines.
+    /// The heavier line is kept if both have the same direction.
+    ///
+    /// Return `None` if any of them isn't a box-drawing char,
+    /// or there isn't a char for the junction, like the heavy line meets the double line.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::Decor;
+    /// assert_eq!(Decor::merge('─', '│'), Some('┼'));
+    /// assert_eq!(Decor::merge('┐', '┌'), Some('┬'));
+    /// assert_eq!(Decor::merge('│', '╭'), Some('├'));
+    /// assert_eq!(Decor::merge('═', '║'), Some('╬'));
+    /// assert_eq!(Decor::merge('━', '║'), None);
+    /// assert_eq!(Decor::merge('─', 'x'), None);
+    /// ```
+    pub fn merge(a: char, b: char) -> Option<char> {
+        let (a, b) = (lines_of(a)?, lines_of(b)?);
+        let lines: [u8; 4] = std::array::from_fn(|i| a[i].max(b[i]));
+        // the solid ones are before the dashed and rounded ones
+        LINES.iter().find(|(_, l)| *l == lines).map(|&(c, _)| c)
+    }
 }
 
 #[rustfmt::skip]
@@ -131,6 +239,66 @@ impl Decor {
         }
     }
 
+    /// The double line style
+    /// 
+    /// ```text
+    /// ╔═══╦═══╗
+    /// ║   ║   ║
+    /// ╠═══╬═══╣
+    /// ║   ║   ║
+    /// ╚═══╩═══╝
+    /// ```
+    pub fn double() -> Self {
+        Decor {
+            lt: '╔', rt: '╗',
+            lb: '╚', rb: '╝',
+
+                te: '═', 
+            le: '║', re: '║',
+                be: '═',
+
+                        tc: '╦',
+            lc: '╠', cross: '╬', rc: '╣',
+                        bc: '╩',
+
+            sh: '═', sv: '║',
+        }
+    }
+
+    /// The regular style with the rounded corners
+    /// 
+    /// ```text
+    /// ╭───┬───╮
+    /// │   │   │
+    /// ├───┼───┤
+    /// │   │   │
+    /// ╰───┴───╯
+    /// ```
+    pub fn rounded() -> Self {
+        Decor {
+            lt: '╭', rt: '╮',
+            lb: '╰', rb: '╯',
+            ..Decor::simple()
+        }
+    }
+
+    /// The dashed style
+    /// 
+    /// ```text
+    /// ┌╌╌╌┬╌╌╌┐
+    /// ╎   ╎   ╎
+    /// ├╌╌╌┼╌╌╌┤
+    /// ╎   ╎   ╎
+    /// └╌╌╌┴╌╌╌┘
+    /// ```
+    pub fn dashed() -> Self {
+        Decor {
+            te: '╌', be: '╌', sh: '╌',
+            le: '╎', re: '╎', sv: '╎',
+            ..Decor::simple()
+        }
+    }
+
     /// The style for plot
     /// 
     /// ```text
@@ -159,6 +327,27 @@ impl Decor {
 }
 
 pub(crate) fn draw_box(canvas: &mut Canvas, start: (f64, f64), end: (f64, f64), decor: &Decor) {
+    // the borders on the canvas before, they are merged with the new box
+    let before: Vec<((i32, i32), char)> = canvas
+        .text
+        .iter()
+        .filter(|(_, c)| lines_of(c.char()).is_some())
+        .map(|(&pos, c)| (pos, c.char()))
+        .collect();
+    put_box(canvas, start, end, decor);
+    for (pos, old) in before {
+        // untouched by the new box
+        let Some(now) = canvas.text.get(&pos).filter(|c| c.char() != old) else {
+            continue;
+        };
+        if let Some(c) = Decor::merge(old, now.char()) {
+            let c = ColoredChar::with_style(c, now.style());
+            canvas.text.insert(pos, c);
+        }
+    }
+}
+
+fn put_box(canvas: &mut Canvas, start: (f64, f64), end: (f64, f64), decor: &Decor) {
     let (lt, rt, rb, lb) = decor.get_corner();
     let (te, le, be, re) = decor.get_edge();
     let (start_x, start_y) = start;
@@ -172,3 +361,48 @@ pub(crate) fn draw_box(canvas: &mut Canvas, start: (f64, f64), end: (f64, f64),
     canvas.put(end_x, start_y, rb, None);
     canvas.put(end_x, end_y, rt, None);
 }
+
+// the lines of the box-drawing char in the (up, right, down, left), 0 is none, 1 is light, 2 is heavy and 3 is double
+fn lines_of(c: char) -> Option<[u8; 4]> {
+    LINES
+        .iter()
+        .find(|&&(l, _)| l == c)
+        .map(|&(_, lines)| lines)
+}
+
+// all the box-drawing chars from U+2500 to U+257F but the diagonals
+#[rustfmt::skip]
+const LINES: [(char, [u8; 4]); 125] = [
+    ('─', [0, 1, 0, 1]), ('━', [0, 2, 0, 2]), ('│', [1, 0, 1, 0]), ('┃', [2, 0, 2, 0]),
+    ('┄', [0, 1, 0, 1]), ('┅', [0, 2, 0, 2]), ('┆', [1, 0, 1, 0]), ('┇', [2, 0, 2, 0]),
+    ('┈', [0, 1, 0, 1]), ('┉', [0, 2, 0, 2]), ('┊', [1, 0, 1, 0]), ('┋', [2, 0, 2, 0]),
+    ('┌', [0, 1, 1, 0]), ('┍', [0, 2, 1, 0]), ('┎', [0, 1, 2, 0]), ('┏', [0, 2, 2, 0]),
+    ('┐', [0, 0, 1, 1]), ('┑', [0, 0, 1, 2]), ('┒', [0, 0, 2, 1]), ('┓', [0, 0, 2, 2]),
+    ('└', [1, 1, 0, 0]), ('┕', [1, 2, 0, 0]), ('┖', [2, 1, 0, 0]), ('┗', [2, 2, 0, 0]),
+    ('┘', [1, 0, 0, 1]), ('┙', [1, 0, 0, 2]), ('┚', [2, 0, 0, 1]), ('┛', [2, 0, 0, 2]),
+    ('├', [1, 1, 1, 0]), ('┝', [1, 2, 1, 0]), ('┞', [2, 1, 1, 0]), ('┟', [1, 1, 2, 0]),
+    ('┠', [2, 1, 2, 0]), ('┡', [2, 2, 1, 0]), ('┢', [1, 2, 2, 0]), ('┣', [2, 2, 2, 0]),
+    ('┤', [1, 0, 1, 1]), ('┥', [1, 0, 1, 2]), ('┦', [2, 0, 1, 1]), ('┧', [1, 0, 2, 1]),
+    ('┨', [2, 0, 2, 1]), ('┩', [2, 0, 1, 2]), ('┪', [1, 0, 2, 2]), ('┫', [2, 0, 2, 2]),
+    ('┬', [0, 1, 1, 1]), ('┭', [0, 1, 1, 2]), ('┮', [0, 2, 1, 1]), ('┯', [0, 2, 1, 2]),
+    ('┰', [0, 1, 2, 1]), ('┱', [0, 1, 2, 2]), ('┲', [0, 2, 2, 1]), ('┳', [0, 2, 2, 2]),
+    ('┴', [1, 1, 0, 1]), ('┵', [1, 1, 0, 2]), ('┶', [1, 2, 0, 1]), ('┷', [1, 2, 0, 2]),
+    ('┸', [2, 1, 0, 1]), ('┹', [2, 1, 0, 2]), ('┺', [2, 2, 0, 1]), ('┻', [2, 2, 0, 2]),
+    ('┼', [1, 1, 1, 1]), ('┽', [1, 1, 1, 2]), ('┾', [1, 2, 1, 1]), ('┿', [1, 2, 1, 2]),
+    ('╀', [2, 1, 1, 1]), ('╁', [1, 1, 2, 1]), ('╂', [2, 1, 2, 1]), ('╃', [2, 1, 1, 2]),
+    ('╄', [2, 2, 1, 1]), ('╅', [1, 1, 2, 2]), ('╆', [1, 2, 2, 1]), ('╇', [2, 2, 1, 2]),
+    ('╈', [1, 2, 2, 2]), ('╉', [2, 1, 2, 2]), ('╊', [2, 2, 2, 1]), ('╋', [2, 2, 2, 2]),
+    ('╌', [0, 1, 0, 1]), ('╍', [0, 2, 0, 2]), ('╎', [1, 0, 1, 0]), ('╏', [2, 0, 2, 0]),
+    ('═', [0, 3, 0, 3]), ('║', [3, 0, 3, 0]), ('╒', [0, 3, 1, 0]), ('╓', [0, 1, 3, 0]),
+    ('╔', [0, 3, 3, 0]), ('╕', [0, 0, 1, 3]), ('╖', [0, 0, 3, 1]), ('╗', [0, 0, 3, 3]),
+    ('╘', [1, 3, 0, 0]), ('╙', [3, 1, 0, 0]), ('╚', [3, 3, 0, 0]), ('╛', [1, 0, 0, 3]),
+    ('╜', [3, 0, 0, 1]), ('╝', [3, 0, 0, 3]), ('╞', [1, 3, 1, 0]), ('╟', [3, 1, 3, 0]),
+    ('╠', [3, 3, 3, 0]), ('╡', [1, 0, 1, 3]), ('╢', [3, 0, 3, 1]), ('╣', [3, 0, 3, 3]),
+    ('╤', [0, 3, 1, 3]), ('╥', [0, 1, 3, 1]), ('╦', [0, 3, 3, 3]), ('╧', [1, 3, 0, 3]),
+    ('╨', [3, 1, 0, 1]), ('╩', [3, 3, 0, 3]), ('╪', [1, 3, 1, 3]), ('╫', [3, 1, 3, 1]),
+    ('╬', [3, 3, 3, 3]), ('╭', [0, 1, 1, 0]), ('╮', [0, 0, 1, 1]), ('╯', [1, 0, 0, 1]),
+    ('╰', [1, 1, 0, 0]), ('╴', [0, 0, 0, 1]), ('╵', [1, 0, 0, 0]), ('╶', [0, 1, 0, 0]),
+    ('╷', [0, 0, 1, 0]), ('╸', [0, 0, 0, 2]), ('╹', [2, 0, 0, 0]), ('╺', [0, 2, 0, 0]),
+    ('╻', [0, 0, 2, 0]), ('╼', [0, 2, 0, 1]), ('╽', [1, 0, 2, 0]), ('╾', [0, 1, 0, 2]),
+    ('╿', [2, 0, 1, 0]),
+];
@@ -1,6 +1,6 @@
 use crate::{
-    extra::{math::Figure, LifeGame, Object3D, Turtle},
-    Animation, Canvas,
+    extra::{math::Figure, LifeGame, Object3D, Particles, SpriteEngine, Turtle},
+    Animation, Canvas, LayeredCanvas, Recorder, SharedCanvas,
 };
 
 impl Default for Canvas {
@@ -15,6 +15,12 @@ impl Default for Object3D {
     }
 }
 
+impl Default for Particles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Default for Turtle {
     fn default() -> Self {
         Self::new()
@@ -38,3 +44,27 @@ impl Default for Figure {
         Self::new()
     }
 }
+
+impl Default for LayeredCanvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for SharedCanvas {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for SpriteEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
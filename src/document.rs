@@ -0,0 +1,362 @@
+// the portable document format of rsille (.rsille)
+//
+// all the numbers are little endian:
+//
+// document: magic "RSILLE" | version: u8 | layers: u32 | layer * layers
+// layer:    name: str | visible: u8 | minx: f64 | miny: f64 | width: i32 | height: i32
+//           | pixels: u32 | (col: i32, row: i32, code: u8, fg: color, bg: color) * pixels
+//           the code is always the braille, the other glyphs are put into the braille cells
+//           | chars: u32 | (col: i32, row: i32, char: u32, fg: color, bg: color, attrs: u8, ul: color) * chars
+// attrs:    the bits of bold, italic, underline, strikethrough, blink, double underline, curly underline
+//           from the lowest
+// str:      len: u32 | utf8 bytes
+// color:    tag: u8, 0 is no color, 1..=17 are the named colors,
+//           18 is rgb and followed by r, g, b: u8, 19 is ansi value and followed by value: u8
+//
+// IMPORTANT: never change the format of a released version, add a new version instead
+
+use std::{collections::HashMap, fs};
+
+use crate::{
+    braille::{same_dots, Glyphs, Pixel},
+    color::{Color, Colored, ColoredChar, TextStyle, Underline},
+    utils::{ErrKind, RsilleErr},
+    Canvas,
+};
+
+const MAGIC: &[u8] = b"RSILLE";
+const VERSION: u8 = 1;
+
+const NAMED: [Color; 17] = [
+    Color::Reset,
+    Color::Black,
+    Color::DarkGrey,
+    Color::Red,
+    Color::DarkRed,
+    Color::Green,
+    Color::DarkGreen,
+    Color::Yellow,
+    Color::DarkYellow,
+    Color::Blue,
+    Color::DarkBlue,
+    Color::Magenta,
+    Color::DarkMagenta,
+    Color::Cyan,
+    Color::DarkCyan,
+    Color::White,
+    Color::Grey,
+];
+
+// (name, visible, canvas)
+pub(crate) type Layer = (String, bool, Canvas);
+
+pub(crate) fn encode<'a, I>(layers: I) -> Vec<u8>
+where
+    I: ExactSizeIterator<Item = (&'a str, bool, &'a Canvas)>,
+{
+    let mut buf = Vec::new();
+    buf.extend(MAGIC);
+    buf.push(VERSION);
+    buf.extend((layers.len() as u32).to_le_bytes());
+    for (name, visible, canvas) in layers {
+        // the document is always in the braille
+        let braille;
+        let canvas = if same_dots(canvas.glyphs(), &Glyphs::Braille) {
+            canvas
+        } else {
+            let mut c = canvas.clone();
+            c.set_glyphs(Glyphs::Braille);
+            braille = c;
+            &braille
+        };
+        buf.extend((name.len() as u32).to_le_bytes());
+        buf.extend(name.as_bytes());
+        buf.push(visible as u8);
+        buf.extend(canvas.minx.to_le_bytes());
+        buf.extend(canvas.miny.to_le_bytes());
+        buf.extend(canvas.width.to_le_bytes());
+        buf.extend(canvas.height.to_le_bytes());
+
+        // sort them, so the same canvas is always the same bytes
+        let mut pixels: Vec<_> = canvas.pixels.iter().collect();
+        pixels.sort_by_key(|(&pos, _)| pos);
+        buf.extend((pixels.len() as u32).to_le_bytes());
+        for (&(col, row), pixel) in pixels {
+            buf.extend(col.to_le_bytes());
+            buf.extend(row.to_le_bytes());
+            buf.push(pixel.pixel().code() as u8);
+            encode_color(&mut buf, pixel.foreground());
+            encode_color(&mut buf, pixel.background());
+        }
+
+        let mut text: Vec<_> = canvas.text.iter().collect();
+        text.sort_by_key(|(&pos, _)| pos);
+        buf.extend((text.len() as u32).to_le_bytes());
+        for (&(col, row), c) in text {
+            let style = c.style();
+            buf.extend(col.to_le_bytes());
+            buf.extend(row.to_le_bytes());
+            buf.extend((c.char() as u32).to_le_bytes());
+            encode_color(&mut buf, style.foreground);
+            encode_color(&mut buf, style.background);
+            let attrs = style.bold as u8
+                | (style.italic as u8) << 1
+                | (style.underline.is_some() as u8) << 2
+                | (style.strikethrough as u8) << 3
+                | (style.blink as u8) << 4
+                | ((style.underline == Some(Underline::Double)) as u8) << 5
+                | ((style.underline == Some(Underline::Curly)) as u8) << 6;
+            buf.push(attrs);
+            encode_color(&mut buf, style.underline_color);
+        }
+    }
+    buf
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<Vec<Layer>, RsilleErr> {
+    let mut r = Reader { bytes, pos: 0 };
+    if r.take(MAGIC.len())? != MAGIC {
+        return Err(RsilleErr::with_kind(
+            ErrKind::Parse,
+            "not a rsille document".to_string(),
+        ));
+    }
+    let version = r.u8()?;
+    if version != VERSION {
+        return Err(RsilleErr::with_kind(
+            ErrKind::Parse,
+            format!("unsupported rsille document version: {}", version),
+        ));
+    }
+    let n = r.u32()?;
+    let mut layers = Vec::new();
+    for _ in 0..n {
+        let len = r.u32()? as usize;
+        let name = String::from_utf8(r.take(len)?.to_vec())
+            .map_err(|e| RsilleErr::with_kind(ErrKind::Parse, e.to_string()))?;
+        let visible = r.u8()? != 0;
+        let mut canvas = Canvas::new();
+        canvas.minx = r.f64()?;
+        canvas.miny = r.f64()?;
+        canvas.width = r.i32()?;
+        canvas.height = r.i32()?;
+
+        let mut pixels = HashMap::new();
+        for _ in 0..r.u32()? {
+            let (col, row) = (r.i32()?, r.i32()?);
+            // any u8 is a valid braille code
+            let pixel = unsafe { Pixel::from_unchecked(r.u8()? as u32) };
+            let (fg, bg) = (r.color()?, r.color()?);
+            pixels.insert((col, row), Colored::from_parts(pixel, fg, bg));
+        }
+
+        let mut text = HashMap::new();
+        for _ in 0..r.u32()? {
+            let (col, row) = (r.i32()?, r.i32()?);
+            let Some(c) = char::from_u32(r.u32()?) else {
+                return Err(RsilleErr::with_kind(
+                    ErrKind::Parse,
+                    "invalid char in rsille document".to_string(),
+                ));
+            };
+            let (foreground, background) = (r.color()?, r.color()?);
+            let attrs = r.u8()?;
+            let underline_color = r.color()?;
+            let underline = match (attrs & 4 != 0, attrs & 32 != 0, attrs & 64 != 0) {
+                (false, _, _) => None,
+                (true, true, _) => Some(Underline::Double),
+                (true, _, true) => Some(Underline::Curly),
+                _ => Some(Underline::Single),
+            };
+            let style = TextStyle {
+                foreground,
+                background,
+                bold: attrs & 1 != 0,
+                italic: attrs & 2 != 0,
+                underline,
+                underline_color,
+                strikethrough: attrs & 8 != 0,
+                blink: attrs & 16 != 0,
+            };
+            text.insert((col, row), ColoredChar::with_style(c, style));
+        }
+        canvas.pixels = pixels;
+        canvas.text = text;
+        layers.push((name, visible, canvas));
+    }
+    Ok(layers)
+}
+
+fn encode_color(buf: &mut Vec<u8>, color: Option<Color>) {
+    match color {
+        None => buf.push(0),
+        Some(Color::Rgb { r, g, b }) => buf.extend([18, r, g, b]),
+        Some(Color::AnsiValue(v)) => buf.extend([19, v]),
+        Some(color) => {
+            let i = NAMED.iter().position(|&c| c == color).unwrap_or(0);
+            buf.push(i as u8 + 1);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], RsilleErr> {
+        if self.bytes.len() - self.pos < n {
+            return Err(RsilleErr::with_kind(
+                ErrKind::Parse,
+                "unexpected end of rsille document".to_string(),
+            ));
+        }
+        let v = &self.bytes[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(v)
+    }
+
+    fn u8(&mut self) -> Result<u8, RsilleErr> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, RsilleErr> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32, RsilleErr> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, RsilleErr> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn color(&mut self) -> Result<Option<Color>, RsilleErr> {
+        let color = match self.u8()? {
+            0 => None,
+            18 => Some(Color::Rgb {
+                r: self.u8()?,
+                g: self.u8()?,
+                b: self.u8()?,
+            }),
+            19 => Some(Color::AnsiValue(self.u8()?)),
+            i @ 1..=17 => Some(NAMED[i as usize - 1]),
+            i => {
+                return Err(RsilleErr::with_kind(
+                    ErrKind::Parse,
+                    format!("invalid color in rsille document: {}", i),
+                ))
+            }
+        };
+        Ok(color)
+    }
+}
+
+impl Canvas {
+    /// Encode the canvas to the bytes of the `.rsille` document
+    ///
+    /// The dots, colors, text and the size are all kept,
+    /// so it can be reopened by [`from_bytes`](struct.Canvas.html#method.from_bytes) anywhere.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{color::Color, Canvas};
+    /// let mut c = Canvas::new();
+    /// c.line_colorful((0, 0), (30, 10), Color::Red);
+    /// c.put_text(0, 16, "a red line", None);
+    /// let bytes = c.to_bytes();
+    /// let c2 = Canvas::from_bytes(&bytes).unwrap();
+    /// assert_eq!(bytes, c2.to_bytes());
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode([("", true, self)].into_iter())
+    }
+
+    /// Decode the canvas from the bytes of the `.rsille` document
+    ///
+    /// If there are many layers in the document, those visible layers are composited into one canvas.
+    ///
+    /// Return `err` when the bytes isn't a valid document
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RsilleErr> {
+        let mut canvas = Canvas::new();
+        for (_, visible, layer) in decode(bytes)? {
+            if visible {
+                canvas.merge(&layer);
+            }
+        }
+        Ok(canvas)
+    }
+
+    /// Save the canvas to a `.rsille` file
+    pub fn save(&self, path: &str) -> Result<(), RsilleErr> {
+        fs::write(path, self.to_bytes()).map_err(RsilleErr::from)
+    }
+
+    /// Read the `.rsille` file and build a canvas from it
+    ///
+    /// Return `err` when can't open the file or it isn't a valid document
+    pub fn from_path(path: &str) -> Result<Self, RsilleErr> {
+        let bytes = fs::read(path)
+            .map_err(|e| RsilleErr::from(e).context(format!("can't open rsille file: {}", path)))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use core::fmt;
+
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::Canvas;
+
+    // the canvas is always serialized as the bytes of the `.rsille` document
+    impl Serialize for Canvas {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_bytes(&self.to_bytes())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Canvas {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_bytes(CanvasVisitor)
+        }
+    }
+
+    struct CanvasVisitor;
+
+    impl<'de> de::Visitor<'de> for CanvasVisitor {
+        type Value = Canvas;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "the bytes of a rsille document")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Canvas, E>
+        where
+            E: de::Error,
+        {
+            Canvas::from_bytes(v).map_err(E::custom)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Canvas, A::Error>
+        where
+            A: de::SeqAccess<'de>,
+        {
+            let mut bytes = Vec::new();
+            while let Some(b) = seq.next_element::<u8>()? {
+                bytes.push(b);
+            }
+            self.visit_bytes(&bytes)
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::fs;
+
+use image::{ImageFormat, Rgba, RgbaImage};
+
+use crate::{
+    color::{to_rgb, Color},
+    utils::RsilleErr,
+    Canvas,
+};
+
+pub(crate) const BACKGROUND: (u8, u8, u8) = (0, 0, 0);
+
+impl Canvas {
+    /// Save the canvas as a png image
+    ///
+    /// * `path` - the path of the image
+    /// * `scale` - every dot will be a `scale * scale` square in the image
+    ///
+    /// The background is black and the dots without color are white, just like the terminal.
+    /// The text on the canvas can't be rasterized, so it's ignored,
+    /// use [`save_svg`](struct.Canvas.html#method.save_svg) if you need the text.
+    ///
+    /// Only available with the `export` feature.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use rsille::Canvas;
+    /// let mut c = Canvas::new();
+    /// for x in 0..360 {
+    ///     let x = x as f64;
+    ///     c.set(x / 4.0, x.to_radians().sin() * 20.0);
+    /// }
+    /// c.save_png("sin.png", 4).unwrap();
+    /// ```
+    pub fn save_png(&self, path: &str, scale: u32) -> Result<(), RsilleErr> {
+        self.to_image(scale)
+            .save_with_format(path, ImageFormat::Png)
+            .map_err(RsilleErr::from)
+    }
+
+    // rasterize the canvas, every dot is a `scale * scale` square
+    pub(crate) fn to_image(&self, scale: u32) -> RgbaImage {
+        let scale = scale.max(1);
+        let (width, height, dots) = self.raster();
+        let (r, g, b) = BACKGROUND;
+        let mut img = RgbaImage::from_pixel(
+            width as u32 * scale,
+            height as u32 * scale,
+            Rgba([r, g, b, 255]),
+        );
+        for (x, y, color) in dots {
+            let (r, g, b) = to_rgb(color.unwrap_or(Color::Reset));
+            let (x, y) = (x as u32 * scale, y as u32 * scale);
+            for dy in 0..scale {
+                for dx in 0..scale {
+                    img.put_pixel(x + dx, y + dy, Rgba([r, g, b, 255]));
+                }
+            }
+        }
+        img
+    }
+
+    /// Save the canvas as a svg image
+    ///
+    /// Every dot is a circle and the text is kept as the text.
+    /// The colors are same as [`save_png`](struct.Canvas.html#method.save_png).
+    ///
+    /// Only available with the `export` feature.
+    pub fn save_svg(&self, path: &str) -> Result<(), RsilleErr> {
+        fs::write(path, self.to_svg()).map_err(RsilleErr::from)
+    }
+
+    /// Return the svg of the canvas
+    ///
+    /// Take a look at [`save_svg`](struct.Canvas.html#method.save_svg)
+    pub fn to_svg(&self) -> String {
+        // every dot is 4*4 in the svg, so a braille code is 8*16
+        const DOT: usize = 4;
+        let (width, height, dots) = self.raster();
+        let (w, h) = self.glyphs.size();
+        let (w, h) = (w as usize, h as usize);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+            w = width * DOT,
+            h = height * DOT
+        );
+        svg.push_str(&format!(
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            hex(BACKGROUND)
+        ));
+        for (x, y, color) in dots {
+            svg.push_str(&format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+                x * DOT + DOT / 2,
+                y * DOT + DOT / 2,
+                DOT as f64 * 0.4,
+                hex(to_rgb(color.unwrap_or(Color::Reset)))
+            ));
+        }
+        for (col, row, c, color) in self.raster_text() {
+            if c.is_whitespace() {
+                continue;
+            }
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{}\" fill=\"{}\">{}</text>\n",
+                col * w * DOT,
+                (row + 1) * h * DOT - DOT,
+                h * DOT,
+                hex(to_rgb(color.unwrap_or(Color::Reset))),
+                escape(c)
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn hex(rgb: (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb.0, rgb.1, rgb.2)
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        '"' => "&quot;".to_string(),
+        '\'' => "&apos;".to_string(),
+        _ => c.to_string(),
+    }
+}
@@ -0,0 +1,309 @@
+use std::{collections::VecDeque, f64::consts::PI};
+
+use crate::{canvas::Paint, color::Color, utils::RsilleErr, Canvas};
+
+/// The audio visualizer
+///
+/// Push the `f32` samples from a microphone, a file or anything else,
+/// it keeps the latest samples and paints the waveform or the FFT spectrum of them.
+///
+/// Only available with the `audio` feature.
+///
+/// ## Example
+///
+/// the spectrum of a 440hz sine wave
+/// ```
+/// use rsille::{extra::{Audio, AudioView}, Canvas};
+/// let rate = 8000;
+/// let samples: Vec<f32> = (0..2048)
+///     .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / rate as f32).sin())
+///     .collect();
+/// let mut audio = Audio::new(rate);
+/// audio.push(&samples);
+/// audio.set_view(AudioView::Both);
+/// let (peak, _) = audio.peak().unwrap();
+/// assert!((peak - 440.0).abs() < 10.0);
+/// let mut c = Canvas::new();
+/// c.paint(&audio, 0, 0).unwrap();
+/// c.print();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Audio {
+    samples: VecDeque<f32>,
+    sample_rate: u32,
+    fft_size: usize,
+    window: FftWindow,
+    view: AudioView,
+    db_range: (f64, f64),
+    log_scale: bool,
+    size: (usize, usize),
+    color: Color,
+}
+
+/// The window function applied before the FFT
+///
+/// The window makes the edge of the samples smooth, so the spectrum leaks less
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FftWindow {
+    /// No window
+    Rectangular,
+    /// The Hann window, it's the default
+    Hann,
+    /// The Hamming window
+    Hamming,
+    /// The Blackman window
+    Blackman,
+}
+
+/// What the [`Audio`](struct.Audio.html) paints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioView {
+    /// The samples in time
+    Waveform,
+    /// The spectrum in dB, it's the default
+    Spectrum,
+    /// The waveform above the spectrum
+    Both,
+}
+
+impl Audio {
+    /// Make a new visualizer
+    ///
+    /// * `sample_rate` - the samples in one second, like 44100
+    ///
+    /// The default FFT size is 1024 with the Hann window,
+    /// the dB range is *[-80, 0]* and the size is 120 * 40 dots.
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            sample_rate: sample_rate.max(1),
+            fft_size: 1024,
+            window: FftWindow::Hann,
+            view: AudioView::Spectrum,
+            db_range: (-80.0, 0.0),
+            log_scale: true,
+            size: (120, 40),
+            color: Color::Reset,
+        }
+    }
+
+    /// Push the samples, only the latest `fft_size` samples are kept
+    ///
+    /// The samples should be in *[-1, 1]*
+    pub fn push(&mut self, samples: &[f32]) {
+        self.samples.extend(samples);
+        while self.samples.len() > self.fft_size {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Remove all the samples
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Set the count of samples for the FFT, it will be rounded up to the power of 2, and it's *4* at least
+    ///
+    /// The bigger, the more detail in the frequency but the slower
+    pub fn set_fft_size(&mut self, size: usize) {
+        self.fft_size = size.clamp(4, 1 << 16).next_power_of_two();
+        while self.samples.len() > self.fft_size {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Set the window function
+    pub fn set_window(&mut self, window: FftWindow) {
+        self.window = window;
+    }
+
+    /// Set what to paint
+    pub fn set_view(&mut self, view: AudioView) {
+        self.view = view;
+    }
+
+    /// Set the range of the dB, the lower is the bottom and the upper is the top of the spectrum
+    pub fn set_db_range(&mut self, min: f64, max: f64) {
+        self.db_range = (min.min(max), min.max(max));
+    }
+
+    /// Use the log scale for the frequency or not, the default is `true`
+    ///
+    /// The log scale is close to how people hear
+    pub fn set_log_scale(&mut self, log_scale: bool) {
+        self.log_scale = log_scale;
+    }
+
+    /// Set the size of one view in dots
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.size = (width.max(1), height.max(2));
+    }
+
+    /// Set the color
+    pub fn color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    /// Return the spectrum in dB
+    ///
+    /// The `i` is the frequency of `i * sample_rate / fft_size`, and there are `fft_size / 2` of them.
+    /// The full scale sine wave is about 0 dB.
+    pub fn spectrum(&self) -> Vec<f64> {
+        let n = self.fft_size;
+        // pad the zeros at the beginning if the samples isn't enough
+        let pad = n - self.samples.len();
+        let mut gain = 0.0;
+        let mut buf: Vec<(f64, f64)> = (0..n)
+            .map(|i| {
+                let w = self.window.value(i, n);
+                gain += w;
+                let s = if i < pad {
+                    0.0
+                } else {
+                    self.samples[i - pad] as f64
+                };
+                (s * w, 0.0)
+            })
+            .collect();
+        fft(&mut buf);
+        buf[..n / 2]
+            .iter()
+            .map(|&(re, im)| {
+                let amplitude = (re * re + im * im).sqrt() * 2.0 / gain;
+                20.0 * amplitude.max(1e-12).log10()
+            })
+            .collect()
+    }
+
+    /// Return the `(frequency, dB)` of the loudest frequency, `None` if there isn't any sample
+    pub fn peak(&self) -> Option<(f64, f64)> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let spectrum = self.spectrum();
+        let (i, &db) = spectrum
+            .iter()
+            .enumerate()
+            .skip(1)
+            .max_by(|a, b| a.1.total_cmp(b.1))?;
+        Some((self.frequency(i), db))
+    }
+
+    fn frequency(&self, bin: usize) -> f64 {
+        bin as f64 * self.sample_rate as f64 / self.fft_size as f64
+    }
+
+    fn paint_waveform(&self, canvas: &mut Canvas, x: f64, y: f64) {
+        let (w, h) = self.size;
+        let n = self.samples.len();
+        if n == 0 {
+            return;
+        }
+        let mid = y + (h - 1) as f64 / 2.0;
+        let amp = (h - 1) as f64 / 2.0;
+        let mut prev = None;
+        for col in 0..w {
+            let i = col * n / w;
+            let v = (self.samples[i] as f64).clamp(-1.0, 1.0);
+            let xy = (x + col as f64, mid + v * amp);
+            if let Some(p) = prev {
+                canvas.line_colorful(p, xy, self.color);
+            }
+            prev = Some(xy);
+        }
+    }
+
+    fn paint_spectrum(&self, canvas: &mut Canvas, x: f64, y: f64) {
+        let (w, h) = self.size;
+        if self.samples.is_empty() {
+            return;
+        }
+        let spectrum = self.spectrum();
+        let bins = spectrum.len();
+        // only the DC, nothing to show
+        if bins < 2 {
+            return;
+        }
+        let (min, max) = self.db_range;
+        for col in 0..w {
+            // the bins in this column
+            let (a, b) = if self.log_scale {
+                let f = |c: usize| (bins as f64).powf(c as f64 / w as f64).round() as usize;
+                (f(col), f(col + 1))
+            } else {
+                (col * bins / w, (col + 1) * bins / w)
+            };
+            let (a, b) = (a.min(bins - 1).max(1), b.min(bins).max(1));
+            let db = spectrum[a..b.max(a + 1)]
+                .iter()
+                .fold(f64::NEG_INFINITY, |m, &v| m.max(v));
+            let level = ((db - min) / (max - min)).clamp(0.0, 1.0) * (h - 1) as f64;
+            if level < 1.0 {
+                continue;
+            }
+            canvas.line_colorful((x + col as f64, y), (x + col as f64, y + level), self.color);
+        }
+    }
+}
+
+impl FftWindow {
+    fn value(&self, i: usize, n: usize) -> f64 {
+        let t = 2.0 * PI * i as f64 / (n - 1).max(1) as f64;
+        match self {
+            FftWindow::Rectangular => 1.0,
+            FftWindow::Hann => 0.5 - 0.5 * t.cos(),
+            FftWindow::Hamming => 0.54 - 0.46 * t.cos(),
+            FftWindow::Blackman => 0.42 - 0.5 * t.cos() + 0.08 * (2.0 * t).cos(),
+        }
+    }
+}
+
+impl Paint for Audio {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        match self.view {
+            AudioView::Waveform => self.paint_waveform(canvas, x, y),
+            AudioView::Spectrum => self.paint_spectrum(canvas, x, y),
+            AudioView::Both => {
+                self.paint_spectrum(canvas, x, y);
+                self.paint_waveform(canvas, x, y + self.size.1 as f64 + 4.0);
+            }
+        }
+        Ok(())
+    }
+}
+
+// the in-place radix-2 Cooley-Tukey FFT, the length must be the power of 2
+fn fft(buf: &mut [(f64, f64)]) {
+    let n = buf.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            buf.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (s, c) = (angle * k as f64).sin_cos();
+                let (ar, ai) = buf[start + k];
+                let (br, bi) = buf[start + k + len / 2];
+                let (tr, ti) = (br * c - bi * s, br * s + bi * c);
+                buf[start + k] = (ar + tr, ai + ti);
+                buf[start + k + len / 2] = (ar - tr, ai - ti);
+            }
+        }
+        len <<= 1;
+    }
+}
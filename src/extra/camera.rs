@@ -0,0 +1,228 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::math::glm::{add, length, scale, sub, Mat4, Vec3};
+use crate::utils::{check_zoom, MIN_DIFFERENCE};
+
+/// The perspective camera for [`Object3D`](struct.Object3D.html)
+///
+/// Like the `Object3D`, the `z` is facing up.
+/// The camera is looking at the `target` from the `position`,
+/// the farther the smaller, or the same size with the [orthographic](struct.Camera.html#method.set_orthographic) one.
+/// The view and the projection are the [`Mat4`](math/glm/struct.Mat4.html) like OpenGL.
+///
+/// ## Example
+///
+/// look at a cube from the front top
+/// ```
+/// use rsille::{extra::{Camera, Object3D}, Canvas};
+/// let mut cube = Object3D::cube(30.0);
+/// let mut camera = Camera::new((0.0, -80.0, 40.0), (0.0, 0.0, 0.0));
+/// camera.set_fov(60.0);
+/// camera.orbit(30.0, 0.0);
+/// cube.set_camera(Some(camera));
+/// let mut c = Canvas::new();
+/// c.paint(&cube, 0, 0).unwrap();
+/// c.print();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    position: (f64, f64, f64),
+    target: (f64, f64, f64),
+    fov: f64,
+    near: f64,
+    far: f64,
+    viewport: f64,
+    orthographic: bool,
+}
+
+impl Camera {
+    /// Make a new camera at `position` and looking at the `target`
+    ///
+    /// The default fov is 60°, the near is 1 and the far is 10000,
+    /// and the height of the view is 96 dots (24 rows of the terminal).
+    pub fn new(position: (f64, f64, f64), target: (f64, f64, f64)) -> Self {
+        Self {
+            position,
+            target,
+            fov: 60.0,
+            near: 1.0,
+            far: 10000.0,
+            viewport: 96.0,
+            orthographic: false,
+        }
+    }
+
+    /// Return the position
+    pub fn position(&self) -> (f64, f64, f64) {
+        self.position
+    }
+
+    /// Set the position
+    pub fn set_position(&mut self, position: (f64, f64, f64)) {
+        self.position = position;
+    }
+
+    /// Return the target
+    pub fn target(&self) -> (f64, f64, f64) {
+        self.target
+    }
+
+    /// Set the target
+    pub fn set_target(&mut self, target: (f64, f64, f64)) {
+        self.target = target;
+    }
+
+    /// Return the vertical field of view in degree
+    pub fn fov(&self) -> f64 {
+        self.fov
+    }
+
+    /// Set the vertical field of view in degree, it will be clamped into *[1, 179]*
+    pub fn set_fov(&mut self, fov: f64) {
+        self.fov = fov.clamp(1.0, 179.0);
+    }
+
+    /// Set the near and far clip plane
+    ///
+    /// Anything nearer than `near` or farther than `far` won't be painted
+    pub fn set_clip(&mut self, near: f64, far: f64) {
+        self.near = near.max(MIN_DIFFERENCE);
+        self.far = far.max(self.near);
+    }
+
+    /// Set the height of the view in dots
+    ///
+    /// The things fill the fov vertically will be this height on the canvas
+    pub fn set_viewport(&mut self, height: f64) {
+        self.viewport = height;
+    }
+
+    /// Use the orthographic projection or not, the default is `false`
+    ///
+    /// The things are the same size however far they are,
+    /// and the things at the target are as big as in the perspective one.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{extra::{Camera, Object3D}, Canvas};
+    /// let mut cube = Object3D::cube(30.0);
+    /// let mut camera = Camera::new((60.0, -80.0, 40.0), (0.0, 0.0, 0.0));
+    /// camera.set_orthographic(true);
+    /// cube.set_camera(Some(camera));
+    /// let mut c = Canvas::new();
+    /// c.paint(&cube, 0, 0).unwrap();
+    /// c.print();
+    /// ```
+    pub fn set_orthographic(&mut self, orthographic: bool) {
+        self.orthographic = orthographic;
+    }
+
+    /// Rotate the camera around the target
+    ///
+    /// * `yaw` - the degree rotate about the `z` axis
+    /// * `pitch` - the degree rotate up and down, the camera can't go over the top or the bottom
+    pub fn orbit(&mut self, yaw: f64, pitch: f64) {
+        let (dx, dy, dz) = sub(self.position, self.target);
+        let r = (dx * dx + dy * dy + dz * dz).sqrt();
+        if r < MIN_DIFFERENCE {
+            return;
+        }
+        let theta = dy.atan2(dx) + yaw.to_radians();
+        let limit = 89_f64.to_radians();
+        let phi = ((dz / r).asin() + pitch.to_radians()).clamp(-limit, limit);
+        self.position = add(
+            self.target,
+            (
+                r * phi.cos() * theta.cos(),
+                r * phi.cos() * theta.sin(),
+                r * phi.sin(),
+            ),
+        );
+    }
+
+    /// Move both the camera and the target
+    ///
+    /// * `dx` - move right, negative for left
+    /// * `dy` - move up, negative for down
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        let m = self.view().0;
+        let (right, up) = ((m[0][0], m[0][1], m[0][2]), (m[1][0], m[1][1], m[1][2]));
+        let offset = add(scale(right, dx), scale(up, dy));
+        self.position = add(self.position, offset);
+        self.target = add(self.target, offset);
+    }
+
+    /// Move the camera to the target or away from it
+    ///
+    /// The distance will be `1 / factor` of the original, so bigger than 1 is zoom in.
+    pub fn zoom(&mut self, factor: f64) {
+        check_zoom(factor);
+        let d = sub(self.position, self.target);
+        self.position = add(self.target, scale(d, 1.0 / factor));
+    }
+
+    /// Control the camera by the key
+    ///
+    /// * `←` `→` `↑` `↓` - orbit
+    /// * `shift` + `←` `→` `↑` `↓` - pan
+    /// * `+` `-` - zoom
+    ///
+    /// Return `true` if the key is used
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match (key.code, shift) {
+            (KeyCode::Left, false) => self.orbit(-5.0, 0.0),
+            (KeyCode::Right, false) => self.orbit(5.0, 0.0),
+            (KeyCode::Up, false) => self.orbit(0.0, 5.0),
+            (KeyCode::Down, false) => self.orbit(0.0, -5.0),
+            (KeyCode::Left, true) => self.pan(-2.0, 0.0),
+            (KeyCode::Right, true) => self.pan(2.0, 0.0),
+            (KeyCode::Up, true) => self.pan(0.0, 2.0),
+            (KeyCode::Down, true) => self.pan(0.0, -2.0),
+            (KeyCode::Char('+'), _) | (KeyCode::Char('='), _) => self.zoom(1.1),
+            (KeyCode::Char('-'), _) => self.zoom(1.0 / 1.1),
+            _ => return false,
+        }
+        true
+    }
+
+    // project the points to (x, depth, y), the same layout as the one without camera,
+    // None if it's out of the near and far plane
+    pub(crate) fn project<I>(&self, points: I) -> Vec<Option<Vec3>>
+    where
+        I: IntoIterator<Item = Vec3>,
+    {
+        let (view, projection) = (self.view(), self.projection());
+        let half = self.viewport / 2.0;
+        points
+            .into_iter()
+            .map(|p| {
+                // the camera looks along the -z in the view space
+                let v = view.transform_point(p);
+                let depth = -v.2;
+                if depth < self.near || depth > self.far {
+                    return None;
+                }
+                let (x, y, _) = projection.transform_point(v);
+                Some((x * half, depth, y * half))
+            })
+            .collect()
+    }
+
+    // from the world to the view space, the z of the world is the up
+    fn view(&self) -> Mat4 {
+        Mat4::look_at(self.position, self.target, (0.0, 0.0, 1.0))
+    }
+
+    // from the view space to the [-1, 1] of the fov
+    fn projection(&self) -> Mat4 {
+        if !self.orthographic {
+            return Mat4::perspective(self.fov, 1.0, self.near, self.far);
+        }
+        // the half height of the fov at the target
+        let distance = length(sub(self.target, self.position)).max(self.near);
+        let h = distance * (self.fov.to_radians() / 2.0).tan();
+        Mat4::ortho(-h, h, -h, h, self.near, self.far)
+    }
+}
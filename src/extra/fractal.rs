@@ -0,0 +1,223 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    color::Color,
+    utils::{check_zoom, RsilleErr},
+    Canvas, Paint,
+};
+
+/// The Mandelbrot set and the Julia sets
+///
+/// Every dot is a complex number, and it's iterated by `z = z² + c` until `|z| > 2` or the max iterations.
+/// Without a ramp, the dots in the set are painted.
+/// With a ramp, the dots out of the set are painted and colored by how fast they escape.
+///
+/// The iterations are computed in parallel with the `rayon` feature.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{color::Color, extra::Fractal, Canvas};
+/// let mut mandelbrot = Fractal::mandelbrot();
+/// mandelbrot.set_ramp(&[Color::DarkBlue, Color::Blue, Color::Cyan, Color::White]);
+/// mandelbrot.zoom(1.5);
+/// let mut canvas = Canvas::new();
+/// canvas.paint(&mandelbrot, 0, 0).unwrap();
+/// canvas.print();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fractal {
+    // None for the Mandelbrot set, or the c of the Julia set
+    julia: Option<(f64, f64)>,
+    home: (f64, f64, f64),
+    center: (f64, f64),
+    // the width of the view on the complex plane
+    span: f64,
+    size: (usize, usize),
+    max_iter: u32,
+    ramp: Vec<Color>,
+}
+
+impl Fractal {
+    /// Make the Mandelbrot set, the view is *[-2.5, 1.5]* on the real axis
+    pub fn mandelbrot() -> Self {
+        Self::build(None, (-0.5, 0.0, 4.0))
+    }
+
+    /// Make the Julia set of `c = re + im * i`, like *(-0.8, 0.156)*
+    pub fn julia(re: f64, im: f64) -> Self {
+        Self::build(Some((re, im)), (0.0, 0.0, 3.6))
+    }
+
+    fn build(julia: Option<(f64, f64)>, home: (f64, f64, f64)) -> Self {
+        Self {
+            julia,
+            home,
+            center: (home.0, home.1),
+            span: home.2,
+            size: (120, 80),
+            max_iter: 100,
+            ramp: Vec::new(),
+        }
+    }
+
+    /// Set the size of the painting, it's the dots on the canvas like [`Canvas::set_size`](crate::Canvas::set_size)
+    ///
+    /// The default is *(120, 80)*
+    pub fn set_size(&mut self, width: usize, height: usize) {
+        self.size = (width.max(1), height.max(1));
+    }
+
+    /// Set the max iterations, the more the details but slower, the default is *100*
+    pub fn set_max_iter(&mut self, max_iter: u32) {
+        self.max_iter = max_iter.max(1);
+    }
+
+    /// Return the max iterations
+    pub fn max_iter(&self) -> u32 {
+        self.max_iter
+    }
+
+    /// Set the color ramp, from the fastest escaping to the slowest escaping, which are near the set
+    ///
+    /// Any palette can be used by sampling it to the colors, empty for no colors
+    pub fn set_ramp(&mut self, ramp: &[Color]) {
+        self.ramp = ramp.to_vec();
+    }
+
+    /// Look at `re + im * i` in the center
+    pub fn set_center(&mut self, re: f64, im: f64) {
+        self.center = (re, im);
+    }
+
+    /// Return the complex number in the center
+    pub fn center(&self) -> (f64, f64) {
+        self.center
+    }
+
+    /// Move the view, *0.1* is 10% of the width
+    ///
+    /// * `dx` - move right, negative for left
+    /// * `dy` - move up, negative for down
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        self.center.0 += self.span * dx;
+        self.center.1 += self.span * dy;
+    }
+
+    /// Zoom the view at the center, bigger than 1 is zoom in
+    pub fn zoom(&mut self, factor: f64) {
+        check_zoom(factor);
+        self.span /= factor;
+    }
+
+    /// Go back to the first view
+    pub fn reset_view(&mut self) {
+        self.center = (self.home.0, self.home.1);
+        self.span = self.home.2;
+    }
+
+    /// Control the view by the key
+    ///
+    /// * `←` `→` `↑` `↓` - pan
+    /// * `+` `-` - zoom
+    /// * `[` `]` - less or more iterations
+    /// * `0` - go back to the first view
+    ///
+    /// Return `true` if the key is used, paint again after it
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use crossterm::event::{KeyCode, KeyEvent};
+    /// use rsille::extra::Fractal;
+    /// let mut julia = Fractal::julia(-0.8, 0.156);
+    /// assert!(julia.handle_key(KeyEvent::from(KeyCode::Char(']'))));
+    /// assert_eq!(julia.max_iter(), 200);
+    /// assert!(!julia.handle_key(KeyEvent::from(KeyCode::Char('q'))));
+    /// ```
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Left => self.pan(-0.1, 0.0),
+            KeyCode::Right => self.pan(0.1, 0.0),
+            KeyCode::Up => self.pan(0.0, 0.1),
+            KeyCode::Down => self.pan(0.0, -0.1),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom(1.5),
+            KeyCode::Char('-') => self.zoom(1.0 / 1.5),
+            KeyCode::Char('[') => self.set_max_iter(self.max_iter / 2),
+            KeyCode::Char(']') => self.set_max_iter(self.max_iter * 2),
+            KeyCode::Char('0') => self.reset_view(),
+            _ => return false,
+        }
+        true
+    }
+
+    // the escape time of every dot, row by row from the bottom, None if it's in the set
+    fn escape_times(&self) -> Vec<Vec<Option<f64>>> {
+        let (width, height) = self.size;
+        let row =
+            |j: usize| -> Vec<Option<f64>> { (0..width).map(|i| self.escape_time(i, j)).collect() };
+        #[cfg(feature = "rayon")]
+        return (0..height).into_par_iter().map(row).collect();
+        #[cfg(not(feature = "rayon"))]
+        return (0..height).map(row).collect();
+    }
+
+    fn escape_time(&self, i: usize, j: usize) -> Option<f64> {
+        let (width, height) = self.size;
+        // the dots are square
+        let step = self.span / width as f64;
+        let re = self.center.0 + (i as f64 - width as f64 / 2.0) * step;
+        let im = self.center.1 + (j as f64 - height as f64 / 2.0) * step;
+        let ((mut zr, mut zi), (cr, ci)) = match self.julia {
+            Some(c) => ((re, im), c),
+            None => ((0.0, 0.0), (re, im)),
+        };
+        for n in 0..self.max_iter {
+            let (zr2, zi2) = (zr * zr, zi * zi);
+            if zr2 + zi2 > 4.0 {
+                // the smooth count, so the colors don't have bands
+                let nu = ((zr2 + zi2).ln() / 2.0).ln() / std::f64::consts::LN_2;
+                return Some((n as f64 + 1.0 - nu).max(0.0));
+            }
+            zi = 2.0 * zr * zi + ci;
+            zr = zr2 - zi2 + cr;
+        }
+        None
+    }
+}
+
+impl Paint for Fractal {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        let times = self.escape_times();
+        if self.ramp.is_empty() {
+            let dots = times.iter().enumerate().flat_map(|(j, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, t)| t.is_none())
+                    .map(move |(i, _)| (x + i as f64, y + j as f64))
+            });
+            canvas.set_all(dots, None);
+            return Ok(());
+        }
+        let last = self.ramp.len() - 1;
+        for (j, row) in times.iter().enumerate() {
+            for (i, t) in row.iter().enumerate() {
+                let Some(t) = t else {
+                    continue;
+                };
+                // most of the dots escape fast, so spread them
+                let k = (t / self.max_iter as f64).sqrt() * last as f64;
+                let color = self.ramp[(k.round() as usize).min(last)];
+                canvas.set_colorful(x + i as f64, y + j as f64, color);
+            }
+        }
+        Ok(())
+    }
+}
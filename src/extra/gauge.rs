@@ -0,0 +1,144 @@
+use crate::{canvas::Paint, color::Color, utils::RsilleErr, Canvas};
+
+/// The radial gauge
+///
+/// A ring (or a part of it) filled by the value, every dot of the braille is used,
+/// so it's much smoother than the blocks.
+/// It's painted with the center on *(x, y)*.
+///
+/// ## Example
+///
+/// a semicircle gauge with the label
+/// ```
+/// use rsille::{color::Color, extra::Gauge, Canvas};
+/// let mut gauge = Gauge::new(20.0);
+/// gauge.semicircle();
+/// gauge.set_value(0.7);
+/// gauge.color(Color::Green);
+/// gauge.set_track(Some(Color::DarkGrey));
+/// gauge.show_label(true);
+/// let mut c = Canvas::new();
+/// c.paint(&gauge, 0, 0).unwrap();
+/// c.print();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gauge {
+    value: f64,
+    radius: f64,
+    thickness: f64,
+    start: f64,
+    sweep: f64,
+    color: Color,
+    track: Option<Color>,
+    label: bool,
+}
+
+impl Gauge {
+    /// Make a new gauge with the outer radius in dots
+    ///
+    /// The default is a full ring start from the top and go clockwise,
+    /// the thickness is 3 dots and no track no label.
+    pub fn new(radius: f64) -> Self {
+        Self {
+            value: 0.0,
+            radius: radius.abs(),
+            thickness: 3.0,
+            start: 90.0,
+            sweep: -360.0,
+            color: Color::Reset,
+            track: None,
+            label: false,
+        }
+    }
+
+    /// Set the value, it's in *[0, 1]*
+    pub fn set_value(&mut self, value: f64) {
+        self.value = if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 1.0)
+        };
+    }
+
+    /// Return the value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Set the thickness of the ring in dots
+    pub fn set_thickness(&mut self, thickness: f64) {
+        self.thickness = thickness.abs();
+    }
+
+    /// Set the arc of the gauge
+    ///
+    /// * `start` - the degree where the value 0 is, 0 is right and 90 is up
+    /// * `sweep` - the degree from 0 to 1, the negative is clockwise
+    pub fn set_arc(&mut self, start: f64, sweep: f64) {
+        self.start = start;
+        self.sweep = sweep.clamp(-360.0, 360.0);
+    }
+
+    /// Use the upper semicircle from the left to the right
+    pub fn semicircle(&mut self) {
+        self.set_arc(180.0, -180.0);
+    }
+
+    /// Set the color of the filled part
+    pub fn color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    /// Set the color of the part isn't filled, `None` to hide it
+    ///
+    /// Use `Color::Reset` to show it without color
+    pub fn set_track(&mut self, track: Option<Color>) {
+        self.track = track;
+    }
+
+    /// Show the percentage at the center or not
+    pub fn show_label(&mut self, label: bool) {
+        self.label = label;
+    }
+}
+
+impl Paint for Gauge {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        let (outer, inner) = (self.radius, (self.radius - self.thickness).max(0.0));
+        let r = outer.ceil() as i32;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let (fx, fy) = (dx as f64, dy as f64);
+                let d = fx.hypot(fy);
+                if d >= outer || d < inner {
+                    continue;
+                }
+                // how far from the start along the sweep
+                let angle = fy.atan2(fx).to_degrees() - self.start;
+                let offset = if self.sweep < 0.0 { -angle } else { angle };
+                let t = offset.rem_euclid(360.0) / self.sweep.abs();
+                let color = if t > 1.0 {
+                    continue;
+                } else if t <= self.value && self.value > 0.0 {
+                    self.color
+                } else if let Some(track) = self.track {
+                    track
+                } else {
+                    continue;
+                };
+                canvas.set_colorful(x + fx, y + fy, color);
+            }
+        }
+        if self.label {
+            let text = format!("{:.0}%", self.value * 100.0);
+            // every char takes 2 dots
+            let w = text.chars().count() as f64;
+            canvas.put_text(x - w, y, &text, self.color);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+// the image protocols of the terminals, the image is shown with its real pixels
+
+use image::{codecs::png::PngEncoder, ImageEncoder, RgbaImage};
+
+use crate::utils::RsilleErr;
+
+// the escape sequences of the kitty graphics protocol, the image is sent as the png
+pub(crate) fn kitty(img: &RgbaImage) -> Result<String, RsilleErr> {
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png)
+        .write_image(
+            img.as_raw(),
+            img.width(),
+            img.height(),
+            image::ColorType::Rgba8,
+        )
+        .map_err(RsilleErr::from)?;
+    let data = base64(&png);
+    // the data is sent in chunks of 4096 bytes
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(4096).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = (i + 1 < chunks.len()) as u8;
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            out.push_str(&format!("\x1b_Ga=T,f=100,m={};{}\x1b\\", more, chunk));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    Ok(out)
+}
+
+// the sixel of the image, the colors are reduced to 6 * 6 * 6
+pub(crate) fn sixel(img: &RgbaImage) -> String {
+    let (w, h) = (img.width(), img.height());
+    // the index of the palette, None for the transparent pixel
+    let index: Vec<Option<u8>> = img
+        .pixels()
+        .map(|p| {
+            if p[3] < 128 {
+                return None;
+            }
+            let q = |v: u8| (v as u16 * 5 + 127) / 255;
+            Some((q(p[0]) * 36 + q(p[1]) * 6 + q(p[2])) as u8)
+        })
+        .collect();
+
+    let mut out = format!("\x1bPq\"1;1;{};{}", w, h);
+    for i in 0..216u16 {
+        let v = |c: u16| c * 100 / 5;
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            i,
+            v(i / 36),
+            v(i / 6 % 6),
+            v(i % 6)
+        ));
+    }
+    for top in (0..h).step_by(6) {
+        let rows = (h - top).min(6);
+        let mut used = [false; 216];
+        for y in top..top + rows {
+            for x in 0..w {
+                if let Some(c) = index[(y * w + x) as usize] {
+                    used[c as usize] = true;
+                }
+            }
+        }
+        for (c, _) in used.iter().enumerate().filter(|(_, &u)| u) {
+            out.push_str(&format!("#{}", c));
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..w {
+                let mut bits = 0;
+                for k in 0..rows {
+                    if index[((top + k) * w + x) as usize] == Some(c as u8) {
+                        bits |= 1 << k;
+                    }
+                }
+                let ch = (63 + bits) as u8 as char;
+                match &mut run {
+                    Some((r, n)) if *r == ch => *n += 1,
+                    _ => {
+                        if let Some((r, n)) = run {
+                            push_run(&mut out, r, n);
+                        }
+                        run = Some((ch, 1));
+                    }
+                }
+            }
+            if let Some((r, n)) = run {
+                push_run(&mut out, r, n);
+            }
+            // back to the start of the band for the next color
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_run(out: &mut String, c: char, n: usize) {
+    if n > 3 {
+        out.push_str(&format!("!{}{}", n, c));
+    } else {
+        for _ in 0..n {
+            out.push(c);
+        }
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - i * 6)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
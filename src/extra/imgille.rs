@@ -1,11 +1,19 @@
+use std::{
+    fs::File,
+    io::{BufReader, Write},
+    time::{Duration, Instant},
+};
+
+use super::graphics;
 use crate::{
-    term::get_terminal_size,
-    utils::{get_pos, RsilleErr},
-    Paint,
+    term::{self, get_terminal_size, Graphics},
+    utils::{get_pos, ErrKind, RsilleErr, BAYER},
+    Canvas, DotMatrix, Paint,
 };
 
 use image::{
-    imageops::FilterType::Lanczos3, io::Reader as ImageReader, DynamicImage, GenericImageView,
+    codecs::gif::GifDecoder, imageops::FilterType::Lanczos3, io::Reader as ImageReader,
+    AnimationDecoder, DynamicImage, GenericImageView, ImageFormat,
 };
 
 /// Paint the image on canvas
@@ -28,49 +36,228 @@ use image::{
 /// But if you want, you can move it to other place on the canvas.
 ///
 /// If your image isn't colorful (like grayscale image), you better set the color to `false`.
-/// And give a look at [`thresholds`](#method.thresholds), [`invert`](#method.invert).
-
+/// And give a look at [`thresholds`](#method.thresholds), [`invert`](#method.invert) and [`dither`](#method.dither).
+///
+/// ## Animation
+///
+/// The animated gif is decoded into frames, and it plays with its own delay of every frame,
+/// no matter what the fps of the animation is.
+/// The delay of *10ms* or less is seen as *100ms*, like the browsers do.
+/// ```no_run
+/// use rsille::{extra::Imgille, Animation};
+/// let gif = Imgille::new("path/to/gif").unwrap();
+/// let mut anime = Animation::new();
+/// anime.push(gif, |gif| gif.update(), (0, 0));
+/// anime.run();
+/// ```
+///
+/// or drive the frames by yourself
+/// ```no_run
+/// use rsille::{extra::Imgille, Canvas};
+/// let gif = Imgille::new("path/to/gif").unwrap();
+/// let mut canvas = Canvas::new();
+/// for (frame, delay) in gif.frames() {
+///     canvas.clear();
+///     canvas.paint(&frame, 0, 0).unwrap();
+///     canvas.print();
+///     std::thread::sleep(delay);
+/// }
+/// ```
 #[derive(Debug, Clone)]
 pub struct Imgille {
-    img: DynamicImage,
+    // (image, delay), a still image is one frame
+    frames: Vec<(DynamicImage, Duration)>,
+    current: usize,
+    elapsed: Duration,
+    last_update: Option<Instant>,
+    repeat: bool,
     color: bool,
     thresholds: u8,
     invert: bool,
+    dither: Dither,
+    contrast: f64,
+    brightness: f64,
+    // (cols, rows), None is the rest of the terminal
+    fit: Option<(u32, u32)>,
+    fit_mode: FitMode,
+    cell_aspect: f64,
+}
+
+/// How the [`Imgille`](struct.Imgille.html) fills the area
+///
+/// All of them keep the aspect ratio of the image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitMode {
+    /// Scale the image to be inside the area, it's the default
+    ///
+    /// Without [`fit`](struct.Imgille.html#method.fit), the small image won't be scaled up
+    Contain,
+    /// Scale the image to cover the whole area, and crop the center of it
+    Cover,
+    /// Don't scale the image, only crop the center of it
+    Crop,
+}
+
+/// The way to turn the gray into dots for the [`Imgille`](struct.Imgille.html)
+///
+/// The dot is either on or off, so the gradient will be banded with the plain threshold.
+/// The dithering spreads the dots to fake the gray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dither {
+    /// Compare every pixel with the threshold, it's the default
+    Threshold,
+    /// The Floyd–Steinberg error diffusion, smooth but a little noisy
+    FloydSteinberg,
+    /// The Atkinson error diffusion, more contrast and looks clean
+    Atkinson,
+    /// The 4x4 ordered Bayer matrix, the regular pattern
+    Bayer,
 }
 
 impl Imgille {
     /// Construct a new object contains the picture
     ///
+    /// The gif will be decoded into all its frames.
+    ///
     /// Return `err` when can't open the image or can't decode the image
     pub fn new(path: &str) -> Result<Self, RsilleErr> {
-        let err = Err(RsilleErr::new(format!("can't open image: {}", path)));
-        let img = if let Ok(reader) = ImageReader::open(path) {
-            if let Ok(img) = reader.decode() {
-                img
-            } else {
-                return err;
-            }
-        } else {
-            return err;
-        };
-        Ok(Self {
-            img,
-            color: true,
-            thresholds: 128,
-            invert: false,
-        })
+        let frames = load(path).map_err(|e| e.context(format!("can't open image: {}", path)))?;
+        Ok(Self::from_frames(frames))
+    }
+
+    /// Construct a new object from the image
+    pub fn from_image(img: DynamicImage) -> Self {
+        Self::from_frames(vec![(img, Duration::ZERO)])
+    }
+
+    /// Build the animation from the sequence of image files, like the frames of a video
+    ///
+    /// * `paths` - the images in the order of playing
+    /// * `delay` - how long every frame is shown
+    ///
+    /// Return `err` when can't open any of the images or there isn't any image
+    pub fn from_sequence(paths: &[&str], delay: Duration) -> Result<Self, RsilleErr> {
+        if paths.is_empty() {
+            return Err(RsilleErr::with_kind(
+                ErrKind::NotFound,
+                "no image in the sequence".to_string(),
+            ));
+        }
+        let mut frames = Vec::new();
+        for path in paths {
+            let img = decode(path).map_err(|e| e.context(format!("can't open image: {}", path)))?;
+            frames.push((img, delay));
+        }
+        Ok(Self::from_frames(frames))
     }
 
     /// Open a image file
     pub fn open(&mut self, path: &str) -> Result<(), RsilleErr> {
-        let img = ImageReader::open(path)
-            .map_err(RsilleErr::to_rsille_err)?
-            .decode()
-            .map_err(RsilleErr::to_rsille_err)?;
-        self.img = img;
+        self.frames = load(path)?;
+        self.current = 0;
+        self.elapsed = Duration::ZERO;
+        self.last_update = None;
         Ok(())
     }
 
+    /// Return the count of frames, it's 1 for the still image
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Return the index of the current frame
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// Jump to the frame, do nothing if it's out of range
+    pub fn set_frame(&mut self, index: usize) {
+        if index < self.frames.len() {
+            self.current = index;
+            self.elapsed = Duration::ZERO;
+        }
+    }
+
+    /// Replay from the first frame after the last one or not, the default is `true`
+    pub fn repeat(&mut self, repeat: bool) {
+        self.repeat = repeat;
+    }
+
+    /// Move the playback forward by `dt`
+    ///
+    /// It skips the frames if `dt` is longer than the delay, so the speed is always right.
+    /// Return true if it's at the end and won't repeat
+    pub fn advance(&mut self, dt: Duration) -> bool {
+        self.elapsed += dt;
+        loop {
+            let delay = self.frames[self.current].1;
+            if delay.is_zero() || self.elapsed < delay {
+                break;
+            }
+            if self.current + 1 == self.frames.len() {
+                if !self.repeat {
+                    self.elapsed = Duration::ZERO;
+                    return true;
+                }
+                self.current = 0;
+            } else {
+                self.current += 1;
+            }
+            self.elapsed -= delay;
+        }
+        !self.repeat && self.current + 1 == self.frames.len()
+    }
+
+    /// Move the playback by the real time since the last call
+    ///
+    /// It's made for the [`Animation`](../struct.Animation.html), just like `advance`
+    pub fn update(&mut self) -> bool {
+        let now = Instant::now();
+        let dt = self
+            .last_update
+            .map_or(Duration::ZERO, |last| now.duration_since(last));
+        self.last_update = Some(now);
+        self.advance(dt)
+    }
+
+    /// Return an iterator of all the frames and their delay
+    ///
+    /// Every frame is a still `Imgille` with the same settings
+    pub fn frames(&self) -> impl Iterator<Item = (Imgille, Duration)> + '_ {
+        self.frames.iter().map(|(img, delay)| {
+            let mut frame = Self::from_image(img.clone());
+            frame.color = self.color;
+            frame.thresholds = self.thresholds;
+            frame.invert = self.invert;
+            frame.dither = self.dither;
+            frame.contrast = self.contrast;
+            frame.brightness = self.brightness;
+            frame.fit = self.fit;
+            frame.fit_mode = self.fit_mode;
+            frame.cell_aspect = self.cell_aspect;
+            (frame, *delay)
+        })
+    }
+
+    fn from_frames(frames: Vec<(DynamicImage, Duration)>) -> Self {
+        Self {
+            frames,
+            current: 0,
+            elapsed: Duration::ZERO,
+            last_update: None,
+            repeat: true,
+            color: true,
+            thresholds: 128,
+            invert: false,
+            dither: Dither::Threshold,
+            contrast: 1.0,
+            brightness: 0.0,
+            fit: None,
+            fit_mode: FitMode::Contain,
+            cell_aspect: 2.0,
+        }
+    }
+
     /// Set if the image should be painted with color or not
     ///
     /// The default is `true`, but for grayscale image, you better set it to `false`
@@ -93,6 +280,244 @@ impl Imgille {
     pub fn invert(&mut self, invert: bool) {
         self.invert = invert;
     }
+
+    /// Set the dithering, the default is [`Dither::Threshold`](enum.Dither.html#variant.Threshold)
+    ///
+    /// It's only for when the color is `false`, the thresholds is still the middle gray of the dithering
+    pub fn dither(&mut self, dither: Dither) {
+        self.dither = dither;
+    }
+
+    /// Adjust the contrast before turning the gray into dots
+    ///
+    /// The default is *1.0*, bigger than 1 is more contrast.
+    /// It's only for when the color is `false`.
+    pub fn contrast(&mut self, contrast: f64) {
+        self.contrast = contrast.max(0.0);
+    }
+
+    /// Adjust the brightness before turning the gray into dots
+    ///
+    /// The default is *0.0*, it's added to the gray in *[0, 255]*, so *-255* is all black.
+    /// It's only for when the color is `false`.
+    pub fn brightness(&mut self, brightness: f64) {
+        self.brightness = brightness;
+    }
+
+    /// Paint the image in the area of `width_cells * height_cells` of the terminal
+    ///
+    /// The default is the rest of the terminal from where it's painted.
+    /// Take a look at [`fit_mode`](#method.fit_mode) and [`cell_aspect`](#method.cell_aspect)
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use rsille::{extra::{FitMode, Imgille}, Canvas};
+    /// let mut imgille = Imgille::new("path/to/image").unwrap();
+    /// imgille.fit(40, 20);
+    /// imgille.fit_mode(FitMode::Cover);
+    /// let mut canvas = Canvas::new();
+    /// canvas.paint(&imgille, 0, 0).unwrap();
+    /// canvas.print();
+    /// ```
+    pub fn fit(&mut self, width_cells: u32, height_cells: u32) {
+        self.fit = Some((width_cells.max(1), height_cells.max(1)));
+    }
+
+    /// Fit the rest of the terminal again, it's the default
+    pub fn fit_terminal(&mut self) {
+        self.fit = None;
+    }
+
+    /// Set how the image fills the area
+    pub fn fit_mode(&mut self, mode: FitMode) {
+        self.fit_mode = mode;
+    }
+
+    /// Set the `height / width` of a cell of the terminal
+    ///
+    /// A cell has 2 * 4 dots of the braille, so the dot is only square when the cell is *1:2*.
+    /// With the other [glyphs](crate::Canvas::set_glyphs) of the canvas, the image is fitted by their dots.
+    /// The default is *2.0*, set it if your font is different, or the image will be stretched.
+    pub fn cell_aspect(&mut self, aspect: f64) {
+        if aspect > 0.0 {
+            self.cell_aspect = aspect;
+        }
+    }
+
+    // resize and crop the current frame for the area, one pixel is one dot of the glyphs
+    fn fitted(&self, x: f64, y: f64, glyphs: &dyn DotMatrix) -> DynamicImage {
+        let (w, h) = glyphs.size();
+        let area = self
+            .fit
+            .unwrap_or_else(|| get_rest_size(get_pos(x, y, (w, h))));
+        self.fitted_in(area, (w as f64, h as f64), self.cell_aspect)
+    }
+
+    // resize and crop the current frame for `(cols, rows)` cells,
+    // every cell has `res` pixels and its height is `aspect` times of the width
+    fn fitted_in(&self, (cols, rows): (u32, u32), res: (f64, f64), aspect: f64) -> DynamicImage {
+        // some example for contain with dots (terminal size is 80*24):
+        // 800*240 -> 160*48 (fit the width)
+        // 800*120 -> 160*48 (fit the width)
+        // 800*480 -> 160*96 (fit the height)
+        // 800*960 -> 80*96  (fit the height)
+        let (width, height) = (cols as f64 * res.0, rows as f64 * res.1);
+        let src = &self.frames[self.current].0;
+        // the size in the target pixels if not scaled,
+        // like a dot is `cell_aspect / 2` high when it's 1 wide
+        let (iw, ih) = (
+            src.width() as f64,
+            src.height() as f64 * res.1 / (aspect * res.0),
+        );
+        let scale = match self.fit_mode {
+            FitMode::Contain => {
+                let s = (width / iw).min(height / ih);
+                if self.fit.is_none() {
+                    // the image is so small, no need to resize it
+                    s.min(1.0)
+                } else {
+                    s
+                }
+            }
+            FitMode::Cover => (width / iw).max(height / ih),
+            FitMode::Crop => 1.0,
+        };
+        let (w, h) = (
+            ((iw * scale).round() as u32).max(1),
+            ((ih * scale).round() as u32).max(1),
+        );
+        let img = if (w, h) == (src.width(), src.height()) {
+            src.clone()
+        } else {
+            src.resize_exact(w, h, Lanczos3)
+        };
+        // crop the center
+        let (cw, ch) = (w.min(width as u32), h.min(height as u32));
+        if (cw, ch) == (w, h) {
+            img
+        } else {
+            img.crop_imm((w - cw) / 2, (h - ch) / 2, cw, ch)
+        }
+    }
+
+    // return which pixels should be drawn, in the order of rows
+    fn dots(&self, img: &image::GrayImage) -> Vec<bool> {
+        let (w, h) = (img.width() as usize, img.height() as usize);
+        let mut gray: Vec<f64> = img
+            .pixels()
+            .map(|p| {
+                let v = (p.0[0] as f64 - 128.0) * self.contrast + 128.0 + self.brightness;
+                let v = v.clamp(0.0, 255.0);
+                // make the dots always the dark part
+                if self.invert {
+                    255.0 - v
+                } else {
+                    v
+                }
+            })
+            .collect();
+        let t = if self.invert {
+            255.0 - self.thresholds as f64
+        } else {
+            self.thresholds as f64
+        };
+        // (dx, dy, weight) of the error diffusion
+        let kernel: &[(isize, usize, f64)] = match self.dither {
+            Dither::FloydSteinberg => &[
+                (1, 0, 7.0 / 16.0),
+                (-1, 1, 3.0 / 16.0),
+                (0, 1, 5.0 / 16.0),
+                (1, 1, 1.0 / 16.0),
+            ],
+            // the atkinson only spreads 3/4 of the error
+            Dither::Atkinson => &[
+                (1, 0, 1.0 / 8.0),
+                (2, 0, 1.0 / 8.0),
+                (-1, 1, 1.0 / 8.0),
+                (0, 1, 1.0 / 8.0),
+                (1, 1, 1.0 / 8.0),
+                (0, 2, 1.0 / 8.0),
+            ],
+            Dither::Threshold | Dither::Bayer => &[],
+        };
+        let mut dots = vec![false; w * h];
+        for y in 0..h {
+            for x in 0..w {
+                let v = gray[y * w + x];
+                let dot = match self.dither {
+                    Dither::Bayer => v < t + ((BAYER[y % 4][x % 4] + 0.5) / 16.0 - 0.5) * 255.0,
+                    _ => v < t,
+                };
+                dots[y * w + x] = dot;
+                let err = v - if dot { 0.0 } else { 255.0 };
+                for &(dx, dy, weight) in kernel {
+                    let (nx, ny) = (x as isize + dx, y + dy);
+                    if nx < 0 || nx >= w as isize || ny >= h {
+                        continue;
+                    }
+                    gray[ny * w + nx as usize] += err * weight;
+                }
+            }
+        }
+        dots
+    }
+}
+
+impl Imgille {
+    /// Print the current frame with the best way the terminal supports
+    ///
+    /// Give a look at [`term::graphics`](../term/fn.graphics.html)
+    pub fn print(&self) -> Result<(), RsilleErr> {
+        self.print_with(term::graphics())
+    }
+
+    /// Print the current frame with the `graphics` at the cursor
+    ///
+    /// The [`fit`](struct.Imgille.html#method.fit) and [`fit_mode`](struct.Imgille.html#method.fit_mode) also work,
+    /// but the image is shown with the real pixels if it isn't the braille.
+    /// The options for the dots like [`dither`](struct.Imgille.html#method.dither) are only for the braille.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use rsille::{extra::Imgille, term::Graphics};
+    /// let mut imgille = Imgille::new("path/to/image").unwrap();
+    /// imgille.fit(40, 20);
+    /// imgille.print_with(Graphics::Kitty).unwrap();
+    /// ```
+    pub fn print_with(&self, graphics: Graphics) -> Result<(), RsilleErr> {
+        if graphics == Graphics::Braille {
+            let mut canvas = Canvas::new();
+            canvas.paint(self, 0, 0)?;
+            canvas.print();
+            return Ok(());
+        }
+        let area = self.fit.unwrap_or_else(|| get_rest_size((0, 0)));
+        let (cw, ch) = cell_pixels();
+        let img = self.fitted_in(area, (cw, ch), ch / cw).to_rgba8();
+        let out = match graphics {
+            Graphics::Kitty => graphics::kitty(&img)?,
+            _ => graphics::sixel(&img),
+        };
+        let mut stdout = std::io::stdout();
+        stdout
+            .write_all(out.as_bytes())
+            .and_then(|_| stdout.write_all(b"\n"))
+            .and_then(|_| stdout.flush())
+            .map_err(RsilleErr::from)
+    }
+}
+
+// the (width, height) of a cell in pixels, 10 * 20 if the terminal doesn't tell
+fn cell_pixels() -> (f64, f64) {
+    match crossterm::terminal::window_size() {
+        Ok(s) if s.columns > 0 && s.rows > 0 && s.width > 0 && s.height > 0 => (
+            s.width as f64 / s.columns as f64,
+            s.height as f64 / s.rows as f64,
+        ),
+        _ => (10.0, 20.0),
+    }
 }
 
 impl Paint for Imgille {
@@ -100,57 +525,21 @@ impl Paint for Imgille {
     where
         T: Into<f64>,
     {
-        // some example for resize the image (terminal size is 80*24):
-        // 800*240 -> 160*48 (fit the width)
-        // 800*120 -> 160*48 (fit the width)
-        // 800*480 -> 160*96 (fit the height)
-        // 800*960 -> 80*96  (fit the height)
-        //  img_height      height
-        // ------------ = ---------- and height = 4 * rest_height, width = 2 * rest_width
-        //  img_width       width
-        //
         // important: never optimize with the fill, it's really hard to use and the algo is really complex
         // can't stand use the fill anymore, even it would be musch faster
 
-        // calculate the rest size of the terminal
         let (x, y) = (x.into(), y.into());
-        let (rest_width, rest_height) = get_rest_size(x, y);
-        let (img_width, img_height) = (self.img.width(), self.img.height());
-
-        // check the image is bigger than the terminal or not
-        let img = if img_width > rest_width * 2 || img_height > rest_height * 4 {
-            // first, try to resize the image to fit the height of the terminal
-            let f = rest_height as f64 * 4.0 / img_height as f64;
-            let w = (img_width as f64 * f) as u32;
-            if rest_width * 2 < w {
-                // if the width of the image is still bigger than the terminal width
-                // then resize the image to fit the width of the terminal
-                let f = rest_width as f64 * 2.0 / img_width as f64;
-                let h = (img_height as f64 * f) as u32;
-                self.img.resize_exact(rest_width * 2, h, Lanczos3)
-            } else {
-                self.img.resize_exact(w, rest_height * 4, Lanczos3)
-            }
-        } else {
-            // the image is so small, no need to resize it
-            self.img.clone()
-        };
+        let img = self.fitted(x, y, canvas.glyphs());
 
         if !self.color {
             // no color
-            let img = img.grayscale();
-            let img = img.as_luma8().unwrap();
+            let img = img.to_luma8();
             let (iw, ih) = (img.width(), img.height());
+            let dots = self.dots(&img);
             for ny in 0..ih {
                 for nx in 0..iw {
-                    let pixel = img.get_pixel(nx, ny);
-                    let if_draw = if self.invert {
-                        (pixel.0)[0] > self.thresholds
-                    } else {
-                        (pixel.0)[0] < self.thresholds
-                    };
-                    if if_draw {
-                        canvas.set(x + nx as f64, y + (ih - ny) as f64);
+                    if dots[(ny * iw + nx) as usize] {
+                        canvas.set(x + nx as f64, y + (ih - 1 - ny) as f64);
                     }
                 }
             }
@@ -163,7 +552,7 @@ impl Paint for Imgille {
                     let pixel = img.get_pixel(nx, ny);
                     canvas.set_colorful(
                         x + nx as f64,
-                        y + (ih - ny) as f64,
+                        y + (ih - 1 - ny) as f64,
                         Color::Rgb {
                             r: pixel[0],
                             g: pixel[1],
@@ -177,13 +566,55 @@ impl Paint for Imgille {
     }
 }
 
-fn get_rest_size<T>(x: T, y: T) -> (u32, u32)
-where
-    T: Into<f64>,
-{
-    // calculate the rest size of the terminal
+// all the frames of the gif, or the image as one frame
+fn load(path: &str) -> Result<Vec<(DynamicImage, Duration)>, RsilleErr> {
+    let format = ImageReader::open(path)
+        .and_then(|r| r.with_guessed_format())
+        .map_err(RsilleErr::from)?
+        .format();
+    if format != Some(ImageFormat::Gif) {
+        return Ok(vec![(decode(path)?, Duration::ZERO)]);
+    }
+    let file = File::open(path).map_err(RsilleErr::from)?;
+    let decoder = GifDecoder::new(BufReader::new(file)).map_err(RsilleErr::from)?;
+    let frames = decoder
+        .into_frames()
+        .collect_frames()
+        .map_err(RsilleErr::from)?;
+    if frames.is_empty() {
+        return Err(RsilleErr::with_kind(
+            ErrKind::Parse,
+            format!("no frame in the gif: {}", path),
+        ));
+    }
+    // a still gif is shown forever like the other images
+    if frames.len() == 1 {
+        let img = frames.into_iter().next().unwrap().into_buffer();
+        return Ok(vec![(DynamicImage::ImageRgba8(img), Duration::ZERO)]);
+    }
+    Ok(frames
+        .into_iter()
+        .map(|f| {
+            let mut delay = f.delay().into();
+            // most of the gifs with a tiny delay mean 100ms, like the browsers do
+            if delay <= Duration::from_millis(10) {
+                delay = Duration::from_millis(100);
+            }
+            (DynamicImage::ImageRgba8(f.into_buffer()), delay)
+        })
+        .collect())
+}
+
+fn decode(path: &str) -> Result<DynamicImage, RsilleErr> {
+    ImageReader::open(path)
+        .map_err(RsilleErr::from)?
+        .decode()
+        .map_err(RsilleErr::from)
+}
+
+// the rest size of the terminal from the cell
+fn get_rest_size((start_col, start_row): (i32, i32)) -> (u32, u32) {
     let (tw, th) = get_terminal_size();
-    let (start_col, start_row) = get_pos(x, y);
     let rest_width = if start_col > 0 {
         tw as u32 - start_col as u32
     } else {
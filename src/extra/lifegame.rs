@@ -1,6 +1,29 @@
-use std::{collections::HashMap, fs, iter::Peekable};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    iter::Peekable,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use crate::{utils::RsilleErr, Paint};
+use crossterm::{
+    cursor::MoveTo,
+    event::{
+        self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    term::{self, get_terminal_size},
+    utils::{ErrKind, RsilleErr},
+    Canvas, Paint,
+};
 
 // same as HashSet<(isize, isize)>
 // but when using inplace algorithms, just simply change () to bool or u8
@@ -8,7 +31,8 @@ type LiveCells = HashMap<(isize, isize), ()>;
 
 /// The life game
 ///
-/// It support `rle` file download from the internet
+/// It support `rle` file download from the internet,
+/// and any life-like rule in the `B/S` notation, like the `B36/S23` of HighLife
 ///
 /// ## Example
 ///
@@ -19,10 +43,47 @@ type LiveCells = HashMap<(isize, isize), ()>;
 /// anime.push(lg, |lg| lg.update(), (0, 0));
 /// anime.run();
 /// ```
+///
+/// the replicator of HighLife on a torus
+/// ```
+/// use rsille::{extra::{Boundary, LifeGame}, Canvas};
+/// let mut lg = LifeGame::from("x = 5, y = 5, rule = B36/S23\n2b3o$bo2bo$o3bo$o2bo$3o!").unwrap();
+/// assert_eq!(lg.rule(), "B36/S23");
+/// lg.set_boundary(Boundary::Toroidal(60, 60));
+/// for _ in 0..12 {
+///     lg.update();
+/// }
+/// let mut c = Canvas::new();
+/// c.paint(&lg, 0, 0).unwrap();
+/// c.print();
+/// ```
 #[derive(Debug, Clone)]
 pub struct LifeGame {
     cells: LiveCells,
-    // boundless: bool,
+    rule: Rule,
+    boundary: Boundary,
+    generation: u64,
+}
+
+/// The boundary of the [`LifeGame`](struct.LifeGame.html)
+///
+/// The cells of the bounded and toroidal world are in *[0, width) × [0, height)*,
+/// the *(0, 0)* is the first cell of the `rle`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// The world is infinite, it's the default
+    Infinite,
+    /// The cells out of the `(width, height)` are always dead
+    Bounded(usize, usize),
+    /// The edges of the `(width, height)` are wrapped to the opposite side
+    Toroidal(usize, usize),
+}
+
+// birth[n] and survive[n]: if a cell with n neighbors will be born or survive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rule {
+    birth: [bool; 9],
+    survive: [bool; 9],
 }
 
 impl LifeGame {
@@ -30,13 +91,27 @@ impl LifeGame {
     pub fn new() -> Self {
         Self {
             cells: Default::default(),
-            // boundless: true,
+            rule: Rule::conway(),
+            boundary: Boundary::Infinite,
+            generation: 0,
         }
     }
 
     /// Read the `rle` format string and build a life game from it
     ///
+    /// The topology of the golly after the rule, like `rule = B3/S23:T100,100`,
+    /// is the [`Boundary`](enum.Boundary.html): `T` is toroidal, `P` is bounded and the others are infinite.
+    ///
     /// Return `err` when can't parse the rle string
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::extra::{Boundary, LifeGame};
+    /// let lg = LifeGame::from("x = 3, y = 3, rule = B3/S23:T100,80\nbo$2bo$3o!").unwrap();
+    /// assert_eq!(lg.population(), 5);
+    /// assert_eq!(lg.boundary(), Boundary::Toroidal(100, 80));
+    /// ```
     pub fn from(rle: &str) -> Result<Self, RsilleErr> {
         parse(rle)
     }
@@ -45,51 +120,196 @@ impl LifeGame {
     ///
     /// Return `err` when can't parse the rle file or can't open file
     pub fn from_path(path: &str) -> Result<Self, RsilleErr> {
-        let Ok(rle) = fs::read_to_string(path) else {
-            return Err(RsilleErr::new(format!("can't open rle file: {}", path)));
-        };
+        let rle = fs::read_to_string(path)
+            .map_err(|e| RsilleErr::from(e).context(format!("can't open rle file: {}", path)))?;
         Self::from(&rle)
     }
 
+    /// Set the rule in the `B/S` notation
+    ///
+    /// Both `B36/S23` and the old style `23/36` (survive/birth) are accepted.
+    /// The default is the `B3/S23` of Conway's game of life.
+    ///
+    /// Return `err` when can't parse the rule or the rule has `B0`,
+    /// the `B0` would fill the infinite world in one step, so it isn't supported
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), RsilleErr> {
+        self.rule = Rule::parse(rule)?;
+        Ok(())
+    }
+
+    /// Return the rule in the `B/S` notation
+    pub fn rule(&self) -> String {
+        let digits = |v: &[bool; 9]| -> String {
+            (0..9)
+                .filter(|&i| v[i])
+                .map(|i| char::from(b'0' + i as u8))
+                .collect()
+        };
+        format!(
+            "B{}/S{}",
+            digits(&self.rule.birth),
+            digits(&self.rule.survive)
+        )
+    }
+
+    /// Set the boundary of the world
+    ///
+    /// The cells out of the bounded or toroidal world are removed
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+        self.cells = self
+            .cells
+            .keys()
+            .filter_map(|&(x, y)| self.wrap(x, y))
+            .map(|c| (c, ()))
+            .collect();
+    }
+
+    /// Return the boundary of the world
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Return how many times the cells are updated
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Return the count of the live cells
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Make the dead cell alive and the live cell dead
+    ///
+    /// Do nothing if the cell is out of the bounded world, the toroidal world wraps it
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::extra::LifeGame;
+    /// let mut blinker = LifeGame::new();
+    /// for x in 0..3 {
+    ///     blinker.toggle(x, 0);
+    /// }
+    /// blinker.update();
+    /// assert_eq!((blinker.generation(), blinker.population()), (1, 3));
+    /// blinker.toggle(1, 0);
+    /// assert_eq!(blinker.population(), 2);
+    /// ```
+    pub fn toggle(&mut self, x: isize, y: isize) {
+        let Some(pos) = self.wrap(x, y) else {
+            return;
+        };
+        if self.cells.remove(&pos).is_none() {
+            self.cells.insert(pos, ());
+        }
+    }
+
     /// The next moment of the cells
     pub fn update(&mut self) -> bool {
-        let mut next = self.cells.clone();
-        for cell in self.cells.keys() {
-            let (x, y) = *cell;
+        let mut neighbors: HashMap<(isize, isize), usize> = HashMap::new();
+        for &(x, y) in self.cells.keys() {
             for dy in -1..=1 {
                 for dx in -1..=1 {
-                    let neibor = self.count_neighbors(x + dx, y + dy);
-                    if neibor == 3 {
-                        next.insert((x + dx, y + dy), ());
+                    // the cell itself
+                    if dy == 0 && dx == 0 {
+                        continue;
                     }
-
-                    if !(2..4).contains(&neibor) {
-                        next.remove(&(x + dx, y + dy));
+                    if let Some(pos) = self.wrap(x + dx, y + dy) {
+                        *neighbors.entry(pos).or_insert(0) += 1;
                     }
                 }
             }
         }
+        let mut next = LiveCells::new();
+        for (&pos, &n) in &neighbors {
+            let alive = self.cells.contains_key(&pos);
+            if (alive && self.rule.survive[n]) || (!alive && self.rule.birth[n]) {
+                next.insert(pos, ());
+            }
+        }
+        // the lonely cells don't appear in the neighbors
+        if self.rule.survive[0] {
+            for &pos in self.cells.keys() {
+                if !neighbors.contains_key(&pos) {
+                    next.insert(pos, ());
+                }
+            }
+        }
         self.cells = next;
+        self.generation += 1;
 
         false
     }
 
-    fn count_neighbors(&self, x: isize, y: isize) -> usize {
-        let mut count = 0;
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                // the cell itself
-                if dy == 0 && dx == 0 {
-                    continue;
+    // move the cell into the world, None if it's out of the bounded world
+    fn wrap(&self, x: isize, y: isize) -> Option<(isize, isize)> {
+        match self.boundary {
+            Boundary::Infinite => Some((x, y)),
+            Boundary::Bounded(w, h) => {
+                if (0..w as isize).contains(&x) && (0..h as isize).contains(&y) {
+                    Some((x, y))
+                } else {
+                    None
+                }
+            }
+            Boundary::Toroidal(w, h) => {
+                if w == 0 || h == 0 {
+                    return None;
                 }
-                let neighbor_x = x + dx;
-                let neighbor_y = y + dy;
-                if self.cells.contains_key(&(neighbor_x, neighbor_y)) {
-                    count += 1;
+                Some((x.rem_euclid(w as isize), y.rem_euclid(h as isize)))
+            }
+        }
+    }
+}
+
+impl Rule {
+    fn conway() -> Self {
+        let mut rule = Self {
+            birth: [false; 9],
+            survive: [false; 9],
+        };
+        rule.birth[3] = true;
+        rule.survive[2] = true;
+        rule.survive[3] = true;
+        rule
+    }
+
+    fn parse(rule: &str) -> Result<Self, RsilleErr> {
+        let err =
+            || RsilleErr::with_kind(ErrKind::Parse, format!("can't parse the rule: {}", rule));
+        let digits = |s: &str| -> Result<[bool; 9], RsilleErr> {
+            let mut v = [false; 9];
+            for c in s.chars() {
+                match c.to_digit(10) {
+                    Some(d) if d < 9 => v[d as usize] = true,
+                    _ => return Err(err()),
                 }
             }
+            Ok(v)
+        };
+        let Some((a, b)) = rule.trim().split_once('/') else {
+            return Err(err());
+        };
+        let (a, b) = (a.trim(), b.trim());
+        let (birth, survive) = match (a.chars().next(), b.chars().next()) {
+            (Some('B' | 'b'), Some('S' | 's')) => (&a[1..], &b[1..]),
+            (Some('S' | 's'), Some('B' | 'b')) => (&b[1..], &a[1..]),
+            // the old style: survive/birth
+            _ => (b, a),
+        };
+        let parsed = Self {
+            birth: digits(birth)?,
+            survive: digits(survive)?,
+        };
+        if parsed.birth[0] {
+            return Err(RsilleErr::with_kind(
+                ErrKind::Parse,
+                format!("the rule with B0 isn't supported: {}", rule),
+            ));
         }
-        count
+        Ok(parsed)
     }
 }
 
@@ -106,21 +326,252 @@ impl Paint for LifeGame {
     }
 }
 
+/// The interactive editor of the [`LifeGame`](struct.LifeGame.html)
+///
+/// It takes the whole terminal, draw the cells by the mouse and watch them live.
+/// The last row is the status line with the generation and the population.
+///
+/// * `space` - run or pause
+/// * `n` - step one generation, it pauses the game
+/// * `[` `]` - slower or faster
+/// * `click` - toggle the cell
+/// * `←` `→` `↑` `↓` `+` `-` - move and zoom, give a look at [`Canvas::handle_key`](crate::Canvas::handle_key)
+/// * `0` - look at the cells again
+/// * `q` `esc` `ctrl+c` - quit
+///
+/// ## Example
+///
+/// ```no_run
+/// use rsille::extra::{LifeEditor, LifeGame};
+/// let glider = LifeGame::from("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+/// let mut editor = LifeEditor::new(glider);
+/// editor.run().unwrap();
+/// println!("{} cells are alive", editor.game().population());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LifeEditor {
+    game: LifeGame,
+    canvas: Canvas,
+    paused: bool,
+    // generations per second
+    speed: u32,
+}
+
+impl LifeEditor {
+    /// Make an editor of the game, it's paused at first
+    pub fn new(game: LifeGame) -> Self {
+        let mut editor = Self {
+            game,
+            canvas: Canvas::new(),
+            paused: true,
+            speed: 10,
+        };
+        editor.center();
+        editor
+    }
+
+    /// Return the game
+    pub fn game(&self) -> &LifeGame {
+        &self.game
+    }
+
+    /// Return the mutable game
+    pub fn game_mut(&mut self) -> &mut LifeGame {
+        &mut self.game
+    }
+
+    /// Run or pause the game
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Return `true` if the game is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Set how many generations in a second, it's in *[1, 60]* and the default is *10*
+    pub fn set_speed(&mut self, speed: u32) {
+        self.speed = speed.clamp(1, 60);
+    }
+
+    /// Return how many generations in a second
+    pub fn speed(&self) -> u32 {
+        self.speed
+    }
+
+    /// Return the status line, like `gen 12 | pop 34 | running 10/s`
+    pub fn status(&self) -> String {
+        format!(
+            "gen {} | pop {} | {} {}/s | {}",
+            self.game.generation(),
+            self.game.population(),
+            if self.paused { "paused" } else { "running" },
+            self.speed,
+            self.game.rule()
+        )
+    }
+
+    /// Control the game by the key
+    ///
+    /// Return `true` if the key is used
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('n') => {
+                self.paused = true;
+                self.game.update();
+            }
+            KeyCode::Char('[') => self.set_speed(self.speed / 2),
+            KeyCode::Char(']') => self.set_speed(self.speed * 2),
+            // the viewport keeps the cells out of the terminal away
+            KeyCode::Char('0') => self.center(),
+            _ => return self.canvas.handle_key(key),
+        }
+        true
+    }
+
+    /// Toggle the cell under the left click
+    ///
+    /// * `origin` - the *(column, row)* of the terminal where the canvas is printed
+    ///
+    /// Return `true` if a cell is toggled
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, origin: (u16, u16)) -> bool {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return false;
+        }
+        let Some((x, y)) = self.canvas.dot_at(origin, (mouse.column, mouse.row)) else {
+            return false;
+        };
+        self.game.toggle(x.round() as isize, y.round() as isize);
+        true
+    }
+
+    /// Take the whole terminal and run the editor until quit
+    ///
+    /// Return the error if it can't print to the terminal.
+    /// The terminal is restored on the error or the panic.
+    pub fn run(&mut self) -> Result<(), RsilleErr> {
+        // restore the terminal before the panic message, or it's messed up by the raw mode
+        let prev_hook = Arc::new(panic::take_hook());
+        let hook = Arc::clone(&prev_hook);
+        panic::set_hook(Box::new(move |info| {
+            let _ = execute!(std::io::stdout(), event::DisableMouseCapture);
+            term::restore();
+            hook(info);
+        }));
+
+        let mut stdout = std::io::stdout();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.edit(&mut stdout)));
+        let _ = execute!(stdout, event::DisableMouseCapture);
+        term::clear();
+        let _ = execute!(stdout, MoveTo(0, 0));
+        term::restore();
+        // our hook holds the other one, drop it first
+        drop(panic::take_hook());
+        if let Ok(prev_hook) = Arc::try_unwrap(prev_hook) {
+            panic::set_hook(prev_hook);
+        }
+        // let the panic go on
+        result.unwrap_or_else(|e| panic::resume_unwind(e))
+    }
+
+    fn edit(&mut self, stdout: &mut impl Write) -> Result<(), RsilleErr> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout, event::EnableMouseCapture)?;
+        term::hide_cursor();
+        term::clear();
+        let mut last = Instant::now();
+        self.draw(stdout)?;
+        loop {
+            let tick = Duration::from_secs(1) / self.speed;
+            let timeout = if self.paused {
+                tick
+            } else {
+                tick.saturating_sub(last.elapsed())
+            };
+            if event::poll(timeout).unwrap_or(false) {
+                match event::read() {
+                    Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => {
+                        if is_quit(key) {
+                            break;
+                        }
+                        self.handle_key(key);
+                    }
+                    Ok(Event::Mouse(mouse)) => {
+                        self.handle_mouse(mouse, (0, 0));
+                    }
+                    Ok(Event::Resize(_, _)) => term::clear(),
+                    _ => continue,
+                }
+            } else if !self.paused {
+                self.game.update();
+                last = Instant::now();
+            }
+            self.draw(stdout)?;
+        }
+        Ok(())
+    }
+
+    // paint the cells on the whole terminal but the last row, which is the status
+    fn draw(&mut self, w: &mut impl Write) -> Result<(), RsilleErr> {
+        let (cols, rows) = get_terminal_size();
+        let rows = rows.saturating_sub(1);
+        self.canvas.reset();
+        let (dw, dh) = self.canvas.glyphs().size();
+        self.canvas
+            .set_size(cols as f64 * dw as f64, rows as f64 * dh as f64);
+        self.canvas.paint(&self.game, 0, 0)?;
+        self.canvas.print_at(w, 0, 0)?;
+        let mut status = self.status();
+        status.truncate(cols as usize);
+        queue!(
+            w,
+            MoveTo(0, rows),
+            terminal::Clear(ClearType::CurrentLine),
+            Print(status)
+        )
+        .map_err(RsilleErr::from)?;
+        w.flush().map_err(RsilleErr::from)
+    }
+
+    // look at the center of the cells
+    fn center(&mut self) {
+        let cells = self.game.cells.keys();
+        let (minx, miny, maxx, maxy) = cells.fold(
+            (isize::MAX, isize::MAX, isize::MIN, isize::MIN),
+            |(a, b, c, d), &(x, y)| (a.min(x), b.min(y), c.max(x), d.max(y)),
+        );
+        if minx <= maxx {
+            let x = (minx + maxx) as f64 / 2.0;
+            let y = (miny + maxy) as f64 / 2.0;
+            self.canvas.set_viewport(x, y, 1.0);
+        } else {
+            self.canvas.set_viewport(0.0, 0.0, 1.0);
+        }
+    }
+}
+
+fn is_quit(key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => true,
+        KeyCode::Char('c') => key.modifiers == KeyModifiers::CONTROL,
+        _ => false,
+    }
+}
+
 fn parse(rle: &str) -> Result<LifeGame, RsilleErr> {
     let mut lines = rle.lines().peekable();
     let mut cells = HashMap::new();
     // read the head
     // let (width, height) = read_head(&mut lines)?;
-    let _ = read_head(&mut lines)?;
+    let (_, _, rule) = read_head(&mut lines)?;
     // parse
     let (mut x, mut y) = (0_isize, 0_isize);
     let mut count = 0_isize;
     for line in lines {
         let mut chars = line.chars().peekable();
-        loop {
-            let Some(c) = chars.next() else {
-                break;
-            };
+        while let Some(c) = chars.next() {
             match c {
                 'b' => {
                     for _ in 0..=count {
@@ -136,8 +587,9 @@ fn parse(rle: &str) -> Result<LifeGame, RsilleErr> {
                     count = 0;
                 }
                 '$' => {
-                    y += 1;
+                    y += count + 1;
                     x = 0;
+                    count = 0;
                 }
                 '!' => break,
                 '1'..='9' => {
@@ -158,37 +610,79 @@ fn parse(rle: &str) -> Result<LifeGame, RsilleErr> {
             }
         }
     }
+    // the topology of the golly is after the rule
+    let (rule, boundary) = match rule
+        .as_deref()
+        .map(|r| r.split_once(':').unwrap_or((r, "")))
+    {
+        Some((rule, topology)) => (Rule::parse(rule)?, parse_topology(topology)),
+        None => (Rule::conway(), Boundary::Infinite),
+    };
     Ok(LifeGame {
         cells,
-        // boundless: false,
+        rule,
+        boundary,
+        generation: 0,
     })
 }
 
-fn read_head<'a, I>(lines: &mut Peekable<I>) -> Result<(usize, usize), RsilleErr>
+// the topology of the golly like `T100,100`, the unknown ones and the infinite edges are infinite
+fn parse_topology(topology: &str) -> Boundary {
+    let topology = topology.trim();
+    let size = topology
+        .get(1..)
+        .and_then(|s| s.split_once(','))
+        .and_then(
+            |(w, h)| match (w.trim().parse::<usize>(), h.trim().parse::<usize>()) {
+                (Ok(w), Ok(h)) if w > 0 && h > 0 => Some((w, h)),
+                _ => None,
+            },
+        );
+    match (topology.chars().next(), size) {
+        (Some('T' | 't'), Some((w, h))) => Boundary::Toroidal(w, h),
+        (Some('P' | 'p'), Some((w, h))) => Boundary::Bounded(w, h),
+        _ => Boundary::Infinite,
+    }
+}
+
+// (width, height, rule)
+fn read_head<'a, I>(lines: &mut Peekable<I>) -> Result<(usize, usize, Option<String>), RsilleErr>
 where
     I: Iterator<Item = &'a str>,
 {
-    while let Some(line) = lines.peek() {
-        if line.starts_with('#') {
-            lines.next();
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if line.starts_with('#') || line.is_empty() {
             continue;
         }
-        if line.starts_with('x') {
-            let s: Vec<&str> = line.split(',').collect();
-            let width = (s[0].split('=').collect::<Vec<&str>>())[1]
-                .trim()
-                .parse()
-                .unwrap();
-            let height = (s[1].split('=').collect::<Vec<&str>>())[1]
-                .trim()
-                .parse()
-                .unwrap();
-            return Ok((width, height));
-        }
-        if line.is_empty() {
-            lines.next();
-            continue;
+        if !line.starts_with('x') {
+            break;
+        }
+        // the rule is the last and its topology may have the comma, like `rule = B3/S23:T10,10`
+        let (line, rule) = match line.find("rule") {
+            Some(i) => (&line[..i], line[i..].split_once('=').map(|(_, v)| v.trim())),
+            None => (line, None),
+        };
+        let rule = rule.map(|r| r.to_string());
+        let (mut width, mut height) = (None, None);
+        for item in line.split(',') {
+            let Some((key, value)) = item.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "x" => width = value.parse().ok(),
+                "y" => height = value.parse().ok(),
+                _ => (),
+            }
+        }
+        if let (Some(width), Some(height)) = (width, height) {
+            return Ok((width, height, rule));
         }
+        break;
     }
-    Err(RsilleErr::new("can't parse width or height".to_string()))
+    Err(RsilleErr::with_kind(
+        ErrKind::Parse,
+        "can't parse width or height".to_string(),
+    ))
 }
@@ -1,8 +1,12 @@
 use std::iter::zip;
 
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::{Parametric, Polar};
 use crate::{
+    color::Color,
     decor::{draw_box, Decor},
-    utils::MIN_DIFFERENCE,
+    utils::{check_zoom, RsilleErr, MIN_DIFFERENCE},
     Canvas, Paint,
 };
 
@@ -33,48 +37,132 @@ macro_rules! figure {
 pub trait Plotable {
     /// Plot on the figure
     fn plot(&self) -> (Vec<f64>, Vec<f64>);
+
+    /// How to draw the points on the figure
+    ///
+    /// The default is [`Style::Dot`](enum.Style.html#variant.Dot)
+    fn style(&self) -> Style {
+        Style::Dot
+    }
+}
+
+/// How the points of a [`Plotable`](trait.Plotable.html) are drawn on the figure
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    /// Every point is a dot
+    Dot,
+    /// Every point is connected to the next one with a line
+    Line,
+    /// Every point is a marker
+    Marker(Marker),
+    /// Every point is a vertical bar from the 0, and the width of it
+    Bar(f64),
+}
+
+/// The marker for the points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    /// A single dot
+    Dot,
+    /// `+` in dots
+    Plus,
+    /// `x` in dots
+    Cross,
+    /// A small square in dots
+    Square,
+    /// A small diamond in dots
+    Diamond,
+    /// Any character, it will cover the braille code
+    Char(char),
+}
+
+impl Marker {
+    // the dots of the marker, relative to the center
+    fn dots(&self) -> &'static [(f64, f64)] {
+        match self {
+            Marker::Dot | Marker::Char(_) => &[(0.0, 0.0)],
+            Marker::Plus => &[(0.0, 0.0), (1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)],
+            Marker::Cross => &[
+                (0.0, 0.0),
+                (1.0, 1.0),
+                (-1.0, -1.0),
+                (1.0, -1.0),
+                (-1.0, 1.0),
+            ],
+            Marker::Square => &[
+                (-1.0, -1.0),
+                (0.0, -1.0),
+                (1.0, -1.0),
+                (-1.0, 0.0),
+                (1.0, 0.0),
+                (-1.0, 1.0),
+                (0.0, 1.0),
+                (1.0, 1.0),
+            ],
+            Marker::Diamond => &[(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)],
+        }
+    }
 }
 
 /// The figure
 ///
 /// It's a figure can paint many functions on it.
-/// All the math function all automatically draw on the right place.
+/// All the math function all automatically draw on the right place,
+/// the range of axis is calculated from all the plots, and the ticks are labeled with the numbers.
 ///
 /// ## Example
 ///
-/// Draw the `y=sin(x)` and `y=cos(x)`
+/// Draw the `y=sin(x)` and `y=cos(x)` with a legend
 /// ```
-/// use rsille::{extra::math::{Figure, Plot}, Canvas};
+/// use rsille::{color::Color, extra::math::{Figure, Plot}, Canvas};
 ///
 /// let mut canvas = Canvas::new();
 /// let mut figure = Figure::new();
 /// let p1 = Plot::new(|x| x.sin(), (0, 10));
 /// let p2 = Plot::new(|x| x.cos(), (0, 10));
-/// figure.plot(&p1);
-/// figure.plot(&p2);
+/// figure.plot_colorful(&p1, Color::Red);
+/// figure.plot_colorful(&p2, Color::Blue);
+/// figure.legend(&["sin(x)", "cos(x)"]);
+/// figure.grid(true);
 /// canvas.paint(&figure, 0, 0).unwrap();
 /// canvas.print();
 /// ```
 pub struct Figure {
-    xs: Vec<f64>,
-    ys: Vec<f64>,
-    scale: (f64, f64),
+    series: Vec<Series>,
+    size: (f64, f64),
+    xlim: Option<(f64, f64)>,
+    ylim: Option<(f64, f64)>,
     show_axis: bool,
+    show_grid: bool,
+    show_legend: bool,
     boxed: bool,
+    equal_aspect: bool,
     padding: f64,
     decor: Decor,
 }
 
+struct Series {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    style: Style,
+    color: Option<Color>,
+    label: Option<String>,
+}
+
 impl Figure {
     /// Make a new figure
     pub fn new() -> Self {
         Self {
-            xs: Vec::new(),
-            ys: Vec::new(),
-            scale: (12.0, 12.0),
+            series: Vec::new(),
+            size: (120.0, 48.0),
+            xlim: None,
+            ylim: None,
             show_axis: true,
+            show_grid: false,
+            show_legend: false,
             boxed: true,
-            padding: 10.0,
+            equal_aspect: false,
+            padding: 4.0,
             decor: Decor::plot(),
         }
     }
@@ -83,82 +171,421 @@ impl Figure {
     ///
     /// It can plot something impl [`Plotable`](trait.Plotable.html)
     pub fn plot<P>(&mut self, p: &P)
+    where
+        P: Plotable,
+    {
+        self.plot_impl(p, None);
+    }
+
+    /// Similar to [`plot`](struct.Figure.html#method.plot), but it's support color
+    pub fn plot_colorful<P>(&mut self, p: &P, color: Color)
+    where
+        P: Plotable,
+    {
+        self.plot_impl(p, Some(color));
+    }
+
+    /// Set the labels of the plots and show the legend
+    ///
+    /// The labels are used in the order of the plots, like the `legend` in matplotlib
+    pub fn legend(&mut self, labels: &[&str]) {
+        for (series, label) in zip(&mut self.series, labels) {
+            series.label = Some(label.to_string());
+        }
+        self.show_legend = true;
+    }
+
+    /// Set the size of the plotting area
+    ///
+    /// The size is in dots like the [`Canvas`](../../struct.Canvas.html),
+    /// the default is *(120, 48)*, and the axis and labels are outside of it
+    pub fn set_size<T>(&mut self, width: T, height: T)
+    where
+        T: Into<f64>,
+    {
+        self.size = (width.into(), height.into());
+    }
+
+    /// Set the range of x axis
+    ///
+    /// By default, it's calculated from the plots
+    pub fn xlim<T>(&mut self, min: T, max: T)
+    where
+        T: Into<f64>,
+    {
+        self.xlim = Some((min.into(), max.into()));
+    }
+
+    /// Set the range of y axis
+    ///
+    /// By default, it's calculated from the plots
+    pub fn ylim<T>(&mut self, min: T, max: T)
+    where
+        T: Into<f64>,
+    {
+        self.ylim = Some((min.into(), max.into()));
+    }
+
+    /// Show the ticks and labels of axis or not, the default is `true`
+    pub fn show_axis(&mut self, show: bool) {
+        self.show_axis = show;
+    }
+
+    /// Show the grid lines or not, the default is `false`
+    pub fn grid(&mut self, show: bool) {
+        self.show_grid = show;
+    }
+
+    /// Draw the box around the figure or not, the default is `true`
+    pub fn boxed(&mut self, boxed: bool) {
+        self.boxed = boxed;
+    }
+
+    /// Plot the curve of `(x, y) = f(t)`, and the axis use the same scale
+    ///
+    /// It's the same as plotting the [`Parametric`](struct.Parametric.html)
+    /// and [`set_equal_aspect`](struct.Figure.html#method.set_equal_aspect).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{extra::math::Figure, Canvas};
+    /// let mut figure = Figure::new();
+    /// // the orbit of the moon around the earth around the sun
+    /// figure.parametric(
+    ///     |t| (t.cos() + 0.2 * (13.0 * t).cos(), t.sin() + 0.2 * (13.0 * t).sin()),
+    ///     (0.0, std::f64::consts::TAU),
+    /// );
+    /// let mut canvas = Canvas::new();
+    /// canvas.paint(&figure, 0, 0).unwrap();
+    /// canvas.print();
+    /// ```
+    pub fn parametric<F, T>(&mut self, f: F, range: (T, T))
+    where
+        F: Fn(f64) -> (f64, f64),
+        T: Into<f64>,
+    {
+        self.plot(&Parametric::new(f, range));
+        self.equal_aspect = true;
+    }
+
+    /// Plot the curve of `r = f(θ)`, and the axis use the same scale
+    ///
+    /// It's the same as plotting the [`Polar`](struct.Polar.html)
+    /// and [`set_equal_aspect`](struct.Figure.html#method.set_equal_aspect).
+    pub fn polar<F, T>(&mut self, f: F, range: (T, T))
+    where
+        F: Fn(f64) -> f64,
+        T: Into<f64>,
+    {
+        self.plot(&Polar::new(f, range));
+        self.equal_aspect = true;
+    }
+
+    /// Use the same scale on both axis or not, the default is `false`
+    ///
+    /// The range is made larger to fit the size, so the circle is still a circle
+    pub fn set_equal_aspect(&mut self, equal: bool) {
+        self.equal_aspect = equal;
+    }
+
+    /// Move the range of the axis, *0.1* is 10% of the range
+    ///
+    /// * `dx` - move right, negative for left
+    /// * `dy` - move up, negative for down
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        let (xmin, xmax, ymin, ymax) = self.range();
+        let (w, h) = (xmax - xmin, ymax - ymin);
+        self.xlim = Some((xmin + w * dx, xmax + w * dx));
+        self.ylim = Some((ymin + h * dy, ymax + h * dy));
+    }
+
+    /// Zoom the range of the axis at the center, bigger than 1 is zoom in
+    pub fn zoom(&mut self, factor: f64) {
+        check_zoom(factor);
+        let (xmin, xmax, ymin, ymax) = self.range();
+        let scale = |min: f64, max: f64| {
+            let (center, half) = ((min + max) / 2.0, (max - min) / 2.0 / factor);
+            (center - half, center + half)
+        };
+        self.xlim = Some(scale(xmin, xmax));
+        self.ylim = Some(scale(ymin, ymax));
+    }
+
+    /// Control the range of the axis by the key
+    ///
+    /// * `←` `→` `↑` `↓` - pan
+    /// * `+` `-` - zoom
+    ///
+    /// Return `true` if the key is used, paint again after it
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use crossterm::event::{KeyCode, KeyEvent};
+    /// use rsille::extra::math::{Figure, Plot};
+    /// let mut figure = Figure::new();
+    /// figure.plot(&Plot::new(|x| x.sin(), (0, 10)));
+    /// assert!(figure.handle_key(KeyEvent::from(KeyCode::Char('+'))));
+    /// assert!(!figure.handle_key(KeyEvent::from(KeyCode::Char('q'))));
+    /// ```
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Left => self.pan(-0.1, 0.0),
+            KeyCode::Right => self.pan(0.1, 0.0),
+            KeyCode::Up => self.pan(0.0, 0.1),
+            KeyCode::Down => self.pan(0.0, -0.1),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom(1.1),
+            KeyCode::Char('-') => self.zoom(1.0 / 1.1),
+            _ => return false,
+        }
+        true
+    }
+
+    fn plot_impl<P>(&mut self, p: &P, color: Option<Color>)
     where
         P: Plotable,
     {
         let (xs, ys) = p.plot();
-        self.xs.extend(&xs);
-        self.ys.extend(&ys);
+        self.series.push(Series {
+            xs,
+            ys,
+            style: p.style(),
+            color,
+            label: None,
+        });
+    }
+
+    // the (xmin, xmax, ymin, ymax) of the figure
+    fn range(&self) -> (f64, f64, f64, f64) {
+        let (mut xmin, mut xmax) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut ymin, mut ymax) = (f64::INFINITY, f64::NEG_INFINITY);
+        for s in &self.series {
+            let (sxmin, sxmax) = min_max(s.xs.iter());
+            let (symin, symax) = min_max(s.ys.iter().filter(|y| y.is_finite()));
+            // the bars are always start from 0 and have the width
+            let (half, zero) = if let Style::Bar(width) = s.style {
+                (width / 2.0, 0.0)
+            } else {
+                (0.0, symin)
+            };
+            xmin = xmin.min(sxmin - half);
+            xmax = xmax.max(sxmax + half);
+            ymin = ymin.min(symin.min(zero));
+            ymax = ymax.max(symax);
+        }
+        let (xmin, xmax) = self.xlim.unwrap_or((xmin, xmax));
+        let (ymin, ymax) = self.ylim.unwrap_or((ymin, ymax));
+        let (xmin, xmax) = widen(xmin, xmax);
+        let (ymin, ymax) = widen(ymin, ymax);
+        if !self.equal_aspect {
+            return (xmin, xmax, ymin, ymax);
+        }
+        // the same value per dot on both axis
+        let (w, h) = self.size;
+        let scale = ((xmax - xmin) / w).max((ymax - ymin) / h);
+        let (cx, cy) = ((xmin + xmax) / 2.0, (ymin + ymax) / 2.0);
+        let (hw, hh) = (scale * w / 2.0, scale * h / 2.0);
+        (cx - hw, cx + hw, cy - hh, cy + hh)
     }
 }
 
 impl Paint for Figure {
-    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), crate::utils::RsilleErr>
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
     where
         T: Into<f64>,
     {
         let (x, y) = (x.into(), y.into());
-        let pad = self.padding;
-        let (sx, sy) = self.scale;
-        for (px, py) in zip(&self.xs, &self.ys) {
-            canvas.set(x + px * sx, y + py * sy);
-        }
-        if self.boxed || self.show_axis {
-            let minx = self.xs.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-            let maxx = self.xs.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-            let miny = self.ys.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-            let maxy = self.ys.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-            let start = (x + minx * sx - pad, y + miny * sy - pad);
-            let end = (x + maxx * sx + pad, y + maxy * sy + pad);
-            if self.boxed {
-                draw_box(canvas, start, end, &self.decor);
+        let (w, h) = self.size;
+        let (xmin, xmax, ymin, ymax) = self.range();
+        let (sx, sy) = (w / (xmax - xmin), h / (ymax - ymin));
+        let to_x = |v: f64| x + (v - xmin) * sx;
+        let to_y = |v: f64| y + (v - ymin) * sy;
+        let inside = |vx: f64, vy: f64| {
+            vx >= xmin - MIN_DIFFERENCE
+                && vx <= xmax + MIN_DIFFERENCE
+                && vy >= ymin - MIN_DIFFERENCE
+                && vy <= ymax + MIN_DIFFERENCE
+        };
+
+        // the dots of a cell, the text is put by the cells
+        let (cw, ch) = canvas.glyphs().size();
+        let (cw, ch) = (cw as f64, ch as f64);
+        let xticks = ticks(xmin, xmax, w / (12.0 * cw));
+        let yticks = ticks(ymin, ymax, h / (3.0 * ch));
+
+        if self.show_grid {
+            for &(v, _) in &xticks {
+                let mut py = y;
+                while py <= y + h {
+                    canvas.set(to_x(v), py);
+                    py += 3.0;
+                }
             }
+            for &(v, _) in &yticks {
+                let mut px = x;
+                while px <= x + w {
+                    canvas.set(px, to_y(v));
+                    px += 3.0;
+                }
+            }
+        }
 
-            if self.show_axis {
-                let (lc, bc) = (self.decor.lc, self.decor.bc);
-                let (sx, sy) = ((sx / 2.0).floor() * 2.0, (sy / 4.0).floor() * 4.0);
-                let xlabels = gen_label((minx * sx, maxx * sx), sx, minx);
-                for (raw, label) in xlabels {
-                    let (px, _) = floor(raw);
-                    canvas.put(x + label, start.1, bc, None);
-                    canvas.put_text(x + label, start.1 - 4.0, &px, None);
+        let baseline = to_y(0f64.clamp(ymin, ymax));
+        for s in &self.series {
+            let mut last: Option<(f64, f64)> = None;
+            for (&px, &py) in zip(&s.xs, &s.ys) {
+                if !py.is_finite() || !inside(px, py) {
+                    last = None;
+                    continue;
                 }
-                let ylabels = gen_label((miny * sy, maxy * sy), sy, miny);
-                for (raw, label) in ylabels {
-                    let (py, l) = floor(raw);
-                    canvas.put(x + start.0, y + label, lc, None);
-                    canvas.put_text(x + start.0 - 2.0 * l as f64, y + label, &py, None);
+                let (cx, cy) = (to_x(px), to_y(py));
+                match s.style {
+                    Style::Dot => set(canvas, cx, cy, s.color),
+                    Style::Line => {
+                        if let Some(xy) = last {
+                            if let Some(color) = s.color {
+                                canvas.line_colorful(xy, (cx, cy), color);
+                            } else {
+                                canvas.line(xy, (cx, cy));
+                            }
+                        }
+                        set(canvas, cx, cy, s.color);
+                        last = Some((cx, cy));
+                    }
+                    Style::Marker(Marker::Char(c)) => canvas.put(cx, cy, c, s.color),
+                    Style::Marker(marker) => {
+                        for (dx, dy) in marker.dots() {
+                            set(canvas, cx + dx, cy + dy, s.color);
+                        }
+                    }
+                    Style::Bar(width) => {
+                        let (left, right) = (to_x(px - width / 2.0), to_x(px + width / 2.0));
+                        let (bottom, top) = (baseline.min(cy), baseline.max(cy));
+                        // keep a gap of one dot between the bars
+                        let cols = ((right - left).round() as i32 - 1).max(1);
+                        for i in 0..cols {
+                            let mut by = bottom;
+                            while by <= top {
+                                set(canvas, left + i as f64, by, s.color);
+                                by += 1.0;
+                            }
+                        }
+                    }
                 }
             }
         }
-        Ok(())
-    }
-}
 
-fn gen_label(range: (f64, f64), step: f64, v: f64) -> Vec<(f64, f64)> {
-    let mut labels = Vec::new();
-    let mut v = v;
-    let (mut label, max) = (range.0, range.1);
-    let mut p = 0.0;
-    loop {
-        if label > max && (label - max).abs() > 0.01 {
-            break;
+        let pad = self.padding;
+        let start = (x - pad, y - pad);
+        let end = (x + w + pad, y + h + pad);
+        if self.boxed {
+            draw_box(canvas, start, end, &self.decor);
         }
-        if label.abs() - 0.0 < MIN_DIFFERENCE {
-            label = 0.0;
+
+        if self.show_axis {
+            let (lc, bc) = (self.decor.lc, self.decor.bc);
+            for (v, label) in &xticks {
+                let px = to_x(*v);
+                canvas.put(px, start.1, bc, None);
+                // center the label under the tick, every char is a cell
+                let lx = px - (label.chars().count() as f64 - 1.0) * cw / 2.0;
+                canvas.put_text(lx, start.1 - ch, label, None);
+            }
+            for (v, label) in &yticks {
+                let py = to_y(*v);
+                canvas.put(start.0, py, lc, None);
+                let lx = start.0 - cw * (label.chars().count() as f64 + 1.0);
+                canvas.put_text(lx, py, label, None);
+            }
         }
-        if p == 0.0 && label > 0.0 {
-            p = 1.0;
+
+        let labels: Vec<_> = self
+            .series
+            .iter()
+            .filter_map(|s| s.label.as_ref().map(|l| (l, s.color)))
+            .collect();
+        if self.show_legend && !labels.is_empty() {
+            // the legend is on the right top corner inside the figure
+            let len = labels.iter().map(|(l, _)| l.chars().count()).max().unwrap();
+            let lw = cw * (len as f64 + 4.0);
+            let lh = ch * (labels.len() as f64 + 1.0);
+            let lstart = (end.0 - lw - cw, end.1 - lh - ch);
+            let lend = (end.0 - cw, end.1 - ch);
+            // clear the dots under the legend
+            for i in 0..=(lh / ch) as usize {
+                let py = lstart.1 + ch * i as f64;
+                let blank = " ".repeat((lw / cw) as usize + 1);
+                canvas.put_text(lstart.0, py, &blank, None);
+            }
+            draw_box(canvas, lstart, lend, &Decor::simple());
+            for (i, (label, color)) in labels.iter().enumerate() {
+                let py = lend.1 - ch * (i as f64 + 1.0);
+                canvas.put_text(lstart.0 + cw, py, "━", *color);
+                canvas.put_text(lstart.0 + 3.0 * cw, py, label, None);
+            }
         }
-        labels.push((v, label + p));
-        v += 1.0;
-        label += step;
+        Ok(())
+    }
+}
+
+fn set(canvas: &mut Canvas, x: f64, y: f64, color: Option<Color>) {
+    if let Some(color) = color {
+        canvas.set_colorful(x, y, color);
+    } else {
+        canvas.set(x, y);
     }
-    labels
 }
 
-fn floor(v: f64) -> (String, usize) {
-    let a = format!("{v:.2}");
-    let len = a.len();
-    (a, len)
+fn min_max<'a, I>(vs: I) -> (f64, f64)
+where
+    I: Iterator<Item = &'a f64>,
+{
+    vs.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+        (min.min(v), max.max(v))
+    })
+}
+
+// make sure the range isn't empty
+fn widen(min: f64, max: f64) -> (f64, f64) {
+    if !min.is_finite() || !max.is_finite() {
+        (0.0, 1.0)
+    } else if (max - min).abs() < MIN_DIFFERENCE {
+        (min - 1.0, max + 1.0)
+    } else {
+        (min, max)
+    }
+}
+
+// generate about `count` ticks on the nice numbers (1, 2, 5 * 10^n) in the range
+pub(crate) fn ticks(min: f64, max: f64, count: f64) -> Vec<(f64, String)> {
+    let raw = (max - min) / count.max(1.0);
+    let mag = 10f64.powf(raw.log10().floor());
+    let step = match raw / mag {
+        r if r < 1.5 => mag,
+        r if r < 3.5 => 2.0 * mag,
+        r if r < 7.5 => 5.0 * mag,
+        _ => 10.0 * mag,
+    };
+    let decimals = if step >= 1.0 {
+        0
+    } else {
+        (-step.log10()).ceil() as usize
+    };
+    let first = (min / step - MIN_DIFFERENCE).ceil() * step;
+    let n = ((max + MIN_DIFFERENCE - first) / step).floor() + 1.0;
+    if !n.is_finite() || n < 1.0 {
+        return Vec::new();
+    }
+    // count with the integer, the float one stops growing when it's too big
+    let n = (n as usize).min((4.0 * count.max(1.0)) as usize);
+    (0..n)
+        .map(|k| {
+            let mut v = first + k as f64 * step;
+            if v.abs() < MIN_DIFFERENCE {
+                v = 0.0;
+            }
+            (v, format!("{:.*}", decimals, v))
+        })
+        .collect()
 }
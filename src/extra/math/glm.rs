@@ -0,0 +1,364 @@
+//! The vectors, quaternions and matrices for the 3D, like the `glm` of OpenGL
+//!
+//! The vectors are just `(x, y, z)`, and the angles are in degree like the others in this crate.
+//! The matrices work on the column vectors, and the camera looks along `-z` in the view space like OpenGL.
+//!
+//! ## Example
+//!
+//! ```
+//! use rsille::extra::math::glm::{Mat4, Quat};
+//! let q = Quat::from_axis_angle((0.0, 0.0, 1.0), 90.0);
+//! let (x, y, _) = q.rotate((1.0, 0.0, 0.0));
+//! assert!(x.abs() < 1e-9 && (y - 1.0).abs() < 1e-9);
+//!
+//! let view = Mat4::look_at((0.0, -10.0, 5.0), (0.0, 0.0, 0.0), (0.0, 0.0, 1.0));
+//! let p = view.inverse().unwrap().transform_point(view.transform_point((1.0, 2.0, 3.0)));
+//! assert!((p.0 - 1.0).abs() < 1e-9 && (p.1 - 2.0).abs() < 1e-9 && (p.2 - 3.0).abs() < 1e-9);
+//! ```
+
+use std::ops::Mul;
+
+use crate::utils::MIN_DIFFERENCE;
+
+/// The vector in 3D
+pub type Vec3 = (f64, f64, f64);
+
+/// Return `a + b`
+pub fn add(a: Vec3, b: Vec3) -> Vec3 {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+/// Return `a - b`
+pub fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+/// Return `a * k`
+pub fn scale(a: Vec3, k: f64) -> Vec3 {
+    (a.0 * k, a.1 * k, a.2 * k)
+}
+
+/// Return the dot product
+pub fn dot(a: Vec3, b: Vec3) -> f64 {
+    a.0 * b.0 + a.1 * b.1 + a.2 * b.2
+}
+
+/// Return the cross product
+pub fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    (
+        a.1 * b.2 - a.2 * b.1,
+        a.2 * b.0 - a.0 * b.2,
+        a.0 * b.1 - a.1 * b.0,
+    )
+}
+
+/// Return the length
+pub fn length(a: Vec3) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Return the unit vector, `None` for the zero vector
+pub fn normalize(a: Vec3) -> Option<Vec3> {
+    let len = length(a);
+    if len < MIN_DIFFERENCE {
+        None
+    } else {
+        Some(scale(a, 1.0 / len))
+    }
+}
+
+/// The quaternion for the rotation
+///
+/// Multiply them to combine the rotations, `a * b` rotates by `b` first and then `a`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    /// The real part
+    pub w: f64,
+    /// The `i` part
+    pub x: f64,
+    /// The `j` part
+    pub y: f64,
+    /// The `k` part
+    pub z: f64,
+}
+
+impl Quat {
+    /// Make a new quaternion
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// The quaternion doesn't rotate anything
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Rotate `angle` degrees about the `axis` by the right hand rule
+    ///
+    /// It's the identity if the axis is zero
+    pub fn from_axis_angle(axis: Vec3, angle: f64) -> Self {
+        let Some((x, y, z)) = normalize(axis) else {
+            return Self::identity();
+        };
+        let (s, c) = (angle.to_radians() / 2.0).sin_cos();
+        Self::new(c, x * s, y * s, z * s)
+    }
+
+    /// Rotate about the *x*, *y* and *z* axis one by one,
+    /// it's the same as [`Object3D::rotate`](../../struct.Object3D.html#method.rotate)
+    pub fn from_euler(angle: Vec3) -> Self {
+        Self::from_axis_angle((0.0, 0.0, 1.0), angle.2)
+            * Self::from_axis_angle((0.0, 1.0, 0.0), angle.1)
+            * Self::from_axis_angle((1.0, 0.0, 0.0), angle.0)
+    }
+
+    /// Return the length
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Return the unit quaternion, the identity for the zero one
+    pub fn normalize(&self) -> Self {
+        let len = self.length();
+        if len < MIN_DIFFERENCE {
+            return Self::identity();
+        }
+        Self::new(self.w / len, self.x / len, self.y / len, self.z / len)
+    }
+
+    /// Return the conjugate, it's the opposite rotation for the unit quaternion
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Rotate the vector
+    pub fn rotate(&self, v: Vec3) -> Vec3 {
+        let q = (self.x, self.y, self.z);
+        // v + 2w(q x v) + 2q x (q x v)
+        let t = scale(cross(q, v), 2.0);
+        add(add(v, scale(t, self.w)), cross(q, t))
+    }
+
+    /// The spherical linear interpolation, *0* is `self` and *1* is `other`
+    ///
+    /// It always goes the shorter way, so it's smooth for the animation
+    pub fn slerp(&self, other: &Quat, t: f64) -> Self {
+        let (a, mut b) = (self.normalize(), other.normalize());
+        let mut cos = a.dot(&b);
+        if cos < 0.0 {
+            b = Self::new(-b.w, -b.x, -b.y, -b.z);
+            cos = -cos;
+        }
+        // too close, the linear one is good enough
+        let (ka, kb) = if cos > 1.0 - 1e-6 {
+            (1.0 - t, t)
+        } else {
+            let theta = cos.acos();
+            let sin = theta.sin();
+            (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+        };
+        Self::new(
+            a.w * ka + b.w * kb,
+            a.x * ka + b.x * kb,
+            a.y * ka + b.y * kb,
+            a.z * ka + b.z * kb,
+        )
+        .normalize()
+    }
+
+    /// Return the rotation matrix
+    pub fn to_mat4(&self) -> Mat4 {
+        let Quat { w, x, y, z } = self.normalize();
+        Mat4([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+                0.0,
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+                0.0,
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    fn dot(&self, other: &Quat) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, o: Quat) -> Quat {
+        Quat::new(
+            self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+            self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+            self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+            self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+        )
+    }
+}
+
+/// The 4 * 4 matrix, `m[row][col]`
+///
+/// `a * b` applies `b` first and then `a`, like the column vectors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4(pub [[f64; 4]; 4]);
+
+impl Mat4 {
+    /// The identity matrix
+    pub fn identity() -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        Self(m)
+    }
+
+    /// Move by the `offset`
+    pub fn translate(offset: Vec3) -> Self {
+        let mut m = Self::identity();
+        m.0[0][3] = offset.0;
+        m.0[1][3] = offset.1;
+        m.0[2][3] = offset.2;
+        m
+    }
+
+    /// The view matrix of the camera at `eye` looking at the `target`
+    ///
+    /// The `up` is the direction of the up of the world, like `(0, 0, 1)` in this crate
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Self {
+        let f = normalize(sub(target, eye)).unwrap_or((0.0, 1.0, 0.0));
+        // looking along the up, use any other direction
+        let s = normalize(cross(f, up))
+            .or_else(|| normalize(cross(f, (0.0, 1.0, 0.0))))
+            .unwrap_or((1.0, 0.0, 0.0));
+        let u = cross(s, f);
+        Self([
+            [s.0, s.1, s.2, -dot(s, eye)],
+            [u.0, u.1, u.2, -dot(u, eye)],
+            [-f.0, -f.1, -f.2, dot(f, eye)],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// The perspective projection
+    ///
+    /// * `fov` - the vertical field of view in degree
+    /// * `aspect` - the width / the height
+    /// * `near`, `far` - the clip planes, they're mapped to *-1* and *1*
+    pub fn perspective(fov: f64, aspect: f64, near: f64, far: f64) -> Self {
+        let f = 1.0 / (fov.to_radians() / 2.0).tan();
+        let mut m = [[0.0; 4]; 4];
+        m[0][0] = f / aspect;
+        m[1][1] = f;
+        m[2][2] = (far + near) / (near - far);
+        m[2][3] = 2.0 * far * near / (near - far);
+        m[3][2] = -1.0;
+        Self(m)
+    }
+
+    /// The orthographic projection, the box is mapped to *[-1, 1]* on all the axis
+    pub fn ortho(left: f64, right: f64, bottom: f64, top: f64, near: f64, far: f64) -> Self {
+        let mut m = Self::identity().0;
+        m[0][0] = 2.0 / (right - left);
+        m[1][1] = 2.0 / (top - bottom);
+        m[2][2] = -2.0 / (far - near);
+        m[0][3] = -(right + left) / (right - left);
+        m[1][3] = -(top + bottom) / (top - bottom);
+        m[2][3] = -(far + near) / (far - near);
+        Self(m)
+    }
+
+    /// Return the transpose
+    pub fn transpose(&self) -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = self.0[j][i];
+            }
+        }
+        Self(m)
+    }
+
+    /// Return the inverse, `None` if it can't be inverted
+    pub fn inverse(&self) -> Option<Self> {
+        // the Gauss-Jordan elimination with the partial pivoting
+        let mut a = self.0;
+        let mut inv = Self::identity().0;
+        for col in 0..4 {
+            let pivot = (col..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+            if a[pivot][col].abs() < 1e-12 {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+            let k = a[col][col];
+            for j in 0..4 {
+                a[col][j] /= k;
+                inv[col][j] /= k;
+            }
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let k = a[row][col];
+                for j in 0..4 {
+                    a[row][j] -= k * a[col][j];
+                    inv[row][j] -= k * inv[col][j];
+                }
+            }
+        }
+        Some(Self(inv))
+    }
+
+    /// Transform the point, it's divided by the `w` for the projection
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        let m = &self.0;
+        let v = [p.0, p.1, p.2, 1.0];
+        let r: Vec<f64> = (0..4)
+            .map(|i| (0..4).map(|j| m[i][j] * v[j]).sum())
+            .collect();
+        // the point at infinity, just keep the direction
+        if r[3].abs() < MIN_DIFFERENCE {
+            (r[0], r[1], r[2])
+        } else {
+            (r[0] / r[3], r[1] / r[3], r[2] / r[3])
+        }
+    }
+
+    /// Transform the direction, the moving part is ignored
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        let m = &self.0;
+        (
+            m[0][0] * v.0 + m[0][1] * v.1 + m[0][2] * v.2,
+            m[1][0] * v.0 + m[1][1] * v.1 + m[1][2] * v.2,
+            m[2][0] * v.0 + m[2][1] * v.1 + m[2][2] * v.2,
+        )
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, o: Mat4) -> Mat4 {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = (0..4).map(|k| self.0[i][k] * o.0[k][j]).sum();
+            }
+        }
+        Mat4(m)
+    }
+}
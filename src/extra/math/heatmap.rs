@@ -0,0 +1,130 @@
+use crate::{
+    color::Color,
+    utils::{RsilleErr, BAYER},
+    Canvas, Paint,
+};
+
+/// The heatmap
+///
+/// Map a 2D grid of values to the density of braille dots,
+/// the bigger value has more dots, and it can also use a color ramp.
+/// The first row of the grid is on the top.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{extra::math::Heatmap, Canvas};
+/// let grid: Vec<Vec<f64>> = (0..10)
+///     .map(|y| (0..20).map(|x| ((x as f64) / 3.0).sin() + ((y as f64) / 2.0).cos()).collect())
+///     .collect();
+/// let mut heatmap = Heatmap::new(grid);
+/// heatmap.colorful(true);
+/// let mut canvas = Canvas::new();
+/// canvas.paint(&heatmap, 0, 0).unwrap();
+/// canvas.print();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    grid: Vec<Vec<f64>>,
+    cell: (usize, usize),
+    range: Option<(f64, f64)>,
+    colorful: bool,
+}
+
+impl Heatmap {
+    /// Make a heatmap from the grid
+    pub fn new(grid: Vec<Vec<f64>>) -> Self {
+        Self {
+            grid,
+            cell: (2, 4),
+            range: None,
+            colorful: false,
+        }
+    }
+
+    /// Set the size of every cell in dots
+    ///
+    /// The default is *(2, 4)*, it's exactly one braille code
+    pub fn set_cell_size(&mut self, width: usize, height: usize) {
+        self.cell = (width.max(1), height.max(1));
+    }
+
+    /// Set the range of values, the values out of it are clamped
+    ///
+    /// By default, it's the min and max of the grid
+    pub fn set_range<T>(&mut self, range: (T, T))
+    where
+        T: Into<f64>,
+    {
+        self.range = Some((range.0.into(), range.1.into()));
+    }
+
+    /// Use the color ramp (blue -> cyan -> green -> yellow -> red) or not
+    ///
+    /// The default is `false`
+    pub fn colorful(&mut self, colorful: bool) {
+        self.colorful = colorful;
+    }
+
+    fn value_range(&self) -> (f64, f64) {
+        self.range.unwrap_or_else(|| {
+            let values = self.grid.iter().flatten().filter(|v| v.is_finite());
+            values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                (min.min(v), max.max(v))
+            })
+        })
+    }
+}
+
+impl Paint for Heatmap {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        let (min, max) = self.value_range();
+        let span = if max > min { max - min } else { 1.0 };
+        let (cw, ch) = self.cell;
+        let rows = self.grid.len();
+        for (r, row) in self.grid.iter().enumerate() {
+            for (c, v) in row.iter().enumerate() {
+                if !v.is_finite() {
+                    continue;
+                }
+                let t = ((v - min) / span).clamp(0.0, 1.0);
+                let color = ramp(t);
+                for dy in 0..ch {
+                    for dx in 0..cw {
+                        let (px, py) = (c * cw + dx, (rows - 1 - r) * ch + dy);
+                        // always draw a dot for the max value
+                        if t * 16.0 <= BAYER[py % 4][px % 4] && t < 1.0 {
+                            continue;
+                        }
+                        let (px, py) = (x + px as f64, y + py as f64);
+                        if self.colorful {
+                            canvas.set_colorful(px, py, color);
+                        } else {
+                            canvas.set(px, py);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// blue -> cyan -> green -> yellow -> red
+fn ramp(t: f64) -> Color {
+    let (r, g, b) = match t {
+        t if t < 0.25 => (0.0, t * 4.0, 1.0),
+        t if t < 0.5 => (0.0, 1.0, 1.0 - (t - 0.25) * 4.0),
+        t if t < 0.75 => ((t - 0.5) * 4.0, 1.0, 0.0),
+        t => (1.0, 1.0 - (t - 0.75) * 4.0, 0.0),
+    };
+    Color::Rgb {
+        r: (r * 255.0) as u8,
+        g: (g * 255.0) as u8,
+        b: (b * 255.0) as u8,
+    }
+}
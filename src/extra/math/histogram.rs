@@ -0,0 +1,107 @@
+use super::figure::{Plotable, Style};
+
+/// The histogram
+///
+/// Count the data in bins and plot them as bars.
+/// The count of bins is calculated by the [Sturges' formula] by default.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{extra::math::{Figure, Histogram}, Canvas};
+/// let data: Vec<f64> = (0..1000).map(|i| ((i * 7919) % 1000) as f64 / 100.0).collect();
+/// let hist = Histogram::new(&data);
+/// let mut figure = Figure::new();
+/// figure.plot(&hist);
+/// let mut canvas = Canvas::new();
+/// canvas.paint(&figure, 0, 0).unwrap();
+/// canvas.print();
+/// ```
+///
+/// [Sturges' formula]: https://en.wikipedia.org/wiki/Histogram#Sturges'_formula
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    data: Vec<f64>,
+    bins: Option<usize>,
+    range: Option<(f64, f64)>,
+}
+
+impl Histogram {
+    /// Make a histogram of the data
+    ///
+    /// Those data aren't finite (like `NaN`) are ignored
+    pub fn new(data: &[f64]) -> Self {
+        Self {
+            data: data.iter().cloned().filter(|v| v.is_finite()).collect(),
+            bins: None,
+            range: None,
+        }
+    }
+
+    /// Set the count of bins
+    pub fn set_bins(&mut self, bins: usize) {
+        self.bins = Some(bins.max(1));
+    }
+
+    /// Set the range of the bins, the data out of the range are ignored
+    ///
+    /// By default, it's the min and max of the data
+    pub fn set_range<T>(&mut self, range: (T, T))
+    where
+        T: Into<f64>,
+    {
+        self.range = Some((range.0.into(), range.1.into()));
+    }
+
+    /// Return the `(start, end, count)` of every bin
+    pub fn bins(&self) -> Vec<(f64, f64, usize)> {
+        if self.data.is_empty() {
+            return Vec::new();
+        }
+        let n = self
+            .bins
+            .unwrap_or_else(|| (self.data.len() as f64).log2().ceil() as usize + 1);
+        let (min, max) = self.range.unwrap_or_else(|| {
+            let min = self.data.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = self.data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            (min, max)
+        });
+        let width = if max > min {
+            (max - min) / n as f64
+        } else {
+            1.0
+        };
+        let mut counts = vec![0; n];
+        for &v in &self.data {
+            if v < min || v > max {
+                continue;
+            }
+            // the max is in the last bin
+            let i = (((v - min) / width) as usize).min(n - 1);
+            counts[i] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| (min + i as f64 * width, min + (i + 1) as f64 * width, c))
+            .collect()
+    }
+}
+
+impl Plotable for Histogram {
+    fn plot(&self) -> (Vec<f64>, Vec<f64>) {
+        self.bins()
+            .into_iter()
+            .map(|(start, end, count)| ((start + end) / 2.0, count as f64))
+            .unzip()
+    }
+
+    fn style(&self) -> Style {
+        let width = self
+            .bins()
+            .first()
+            .map(|(start, end, _)| end - start)
+            .unwrap_or(1.0);
+        Style::Bar(width)
+    }
+}
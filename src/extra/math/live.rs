@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+
+use super::figure::{Figure, Plotable, Style};
+use crate::{color::Color, utils::RsilleErr, Canvas, Paint};
+
+/// The live streaming plot
+///
+/// It keeps the recent samples in a ring buffer,
+/// every time a new sample is pushed, the oldest one is dropped when the buffer is full,
+/// and the x axis scrolls in real time.
+/// It's ideal for the monitors like cpu or memory usage.
+///
+/// ## Example
+///
+/// ```no_run
+/// use rsille::{extra::math::LivePlot, Animation};
+/// let mut live = LivePlot::new(100);
+/// live.ylim(-1.0, 1.0);
+/// let mut anime = Animation::new();
+/// let mut t = 0.0_f64;
+/// anime.push(live, move |live| {
+///     live.push(t.sin());
+///     t += 0.1;
+///     false
+/// }, (0, 0));
+/// anime.run();
+/// ```
+#[derive(Debug, Clone)]
+pub struct LivePlot {
+    samples: VecDeque<f64>,
+    capacity: usize,
+    pushed: usize,
+    size: (f64, f64),
+    ylim: Option<(f64, f64)>,
+    color: Option<Color>,
+}
+
+impl LivePlot {
+    /// Make a live plot which keeps at most `capacity` samples
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(2);
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            pushed: 0,
+            size: (120.0, 48.0),
+            ylim: None,
+            color: None,
+        }
+    }
+
+    /// Push a new sample, the oldest one is dropped when the buffer is full
+    pub fn push(&mut self, value: f64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+        self.pushed += 1;
+    }
+
+    /// Return the recent samples, from the oldest to the newest
+    pub fn samples(&self) -> Vec<f64> {
+        self.samples.iter().cloned().collect()
+    }
+
+    /// Drop all the samples
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.pushed = 0;
+    }
+
+    /// Set the size of the plotting area
+    ///
+    /// Take a look at [`Figure::set_size`](struct.Figure.html#method.set_size)
+    pub fn set_size<T>(&mut self, width: T, height: T)
+    where
+        T: Into<f64>,
+    {
+        self.size = (width.into(), height.into());
+    }
+
+    /// Set the range of y axis
+    ///
+    /// By default, it's calculated from the samples, so the y axis may change every frame
+    pub fn ylim<T>(&mut self, min: T, max: T)
+    where
+        T: Into<f64>,
+    {
+        self.ylim = Some((min.into(), max.into()));
+    }
+
+    /// Set the color of the line
+    pub fn color(&mut self, color: Color) {
+        self.color = Some(color);
+    }
+}
+
+impl Plotable for LivePlot {
+    fn plot(&self) -> (Vec<f64>, Vec<f64>) {
+        // the index of the first sample in the buffer
+        let start = self.pushed - self.samples.len();
+        let xs = (start..self.pushed).map(|i| i as f64).collect();
+        (xs, self.samples())
+    }
+
+    fn style(&self) -> Style {
+        Style::Line
+    }
+}
+
+impl Paint for LivePlot {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let mut figure = Figure::new();
+        let (w, h) = self.size;
+        figure.set_size(w, h);
+        // always show the whole buffer, so the x axis scrolls with the samples
+        let start = self.pushed.saturating_sub(self.capacity);
+        figure.xlim(start as f64, (start + self.capacity - 1) as f64);
+        if let Some((min, max)) = self.ylim {
+            figure.ylim(min, max);
+        }
+        if let Some(color) = self.color {
+            figure.plot_colorful(self, color);
+        } else {
+            figure.plot(self);
+        }
+        figure.paint(canvas, x, y)
+    }
+}
@@ -8,8 +8,23 @@
 //! ```
 
 mod figure;
+pub mod glm;
+mod heatmap;
+mod histogram;
+mod live;
+mod parametric;
 mod plot;
+mod scatter;
+mod surface;
 
 pub use figure::Figure;
+pub use figure::Marker;
 pub use figure::Plotable;
+pub use figure::Style;
+pub use heatmap::Heatmap;
+pub use histogram::Histogram;
+pub use live::LivePlot;
+pub use parametric::{Parametric, Polar};
 pub use plot::Plot;
+pub use scatter::Scatter;
+pub use surface::Surface;
@@ -0,0 +1,130 @@
+use super::figure::{Plotable, Style};
+
+/// The parametric plot
+///
+/// Plot `(x, y) = f(t)`, like the Lissajous curves and the orbits.
+/// The points are connected with the lines.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{extra::math::{Figure, Parametric}, Canvas};
+/// let lissajous = Parametric::new(|t| ((3.0 * t).sin(), (2.0 * t).sin()), (0.0, 6.3));
+/// let mut figure = Figure::new();
+/// figure.plot(&lissajous);
+/// figure.set_equal_aspect(true);
+/// let mut canvas = Canvas::new();
+/// canvas.paint(&figure, 0, 0).unwrap();
+/// canvas.print();
+/// ```
+#[derive(Debug)]
+pub struct Parametric<F> {
+    f: F,
+    range: (f64, f64),
+    samples: usize,
+}
+
+impl<F> Parametric<F>
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    /// Make a parametric plot, `t` is in the `range`
+    pub fn new<T>(f: F, range: (T, T)) -> Self
+    where
+        T: Into<f64>,
+    {
+        Self {
+            f,
+            range: (range.0.into(), range.1.into()),
+            samples: 1000,
+        }
+    }
+
+    /// Set how many times `f(t)` is calculated, the default is *1000*
+    pub fn set_samples(&mut self, samples: usize) {
+        self.samples = samples.max(2);
+    }
+}
+
+impl<F> Plotable for Parametric<F>
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    fn plot(&self) -> (Vec<f64>, Vec<f64>) {
+        let (start, end) = self.range;
+        let n = self.samples - 1;
+        (0..=n)
+            .map(|i| (self.f)(start + (end - start) * i as f64 / n as f64))
+            .unzip()
+    }
+
+    fn style(&self) -> Style {
+        Style::Line
+    }
+}
+
+/// The polar plot
+///
+/// Plot `r = f(θ)`, the `θ` is in radians, like the roses and the spirals.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{extra::math::{Figure, Polar}, Canvas};
+/// let rose = Polar::new(|theta| (4.0 * theta).cos(), (0.0, std::f64::consts::TAU));
+/// let mut figure = Figure::new();
+/// figure.plot(&rose);
+/// figure.set_equal_aspect(true);
+/// let mut canvas = Canvas::new();
+/// canvas.paint(&figure, 0, 0).unwrap();
+/// canvas.print();
+/// ```
+#[derive(Debug)]
+pub struct Polar<F> {
+    f: F,
+    range: (f64, f64),
+    samples: usize,
+}
+
+impl<F> Polar<F>
+where
+    F: Fn(f64) -> f64,
+{
+    /// Make a polar plot, `θ` is in the `range`
+    pub fn new<T>(f: F, range: (T, T)) -> Self
+    where
+        T: Into<f64>,
+    {
+        Self {
+            f,
+            range: (range.0.into(), range.1.into()),
+            samples: 1000,
+        }
+    }
+
+    /// Set how many times `f(θ)` is calculated, the default is *1000*
+    pub fn set_samples(&mut self, samples: usize) {
+        self.samples = samples.max(2);
+    }
+}
+
+impl<F> Plotable for Polar<F>
+where
+    F: Fn(f64) -> f64,
+{
+    fn plot(&self) -> (Vec<f64>, Vec<f64>) {
+        let (start, end) = self.range;
+        let n = self.samples - 1;
+        (0..=n)
+            .map(|i| {
+                let theta = start + (end - start) * i as f64 / n as f64;
+                let r = (self.f)(theta);
+                (r * theta.cos(), r * theta.sin())
+            })
+            .unzip()
+    }
+
+    fn style(&self) -> Style {
+        Style::Line
+    }
+}
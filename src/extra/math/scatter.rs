@@ -0,0 +1,69 @@
+use std::iter::zip;
+
+use super::figure::{Marker, Plotable, Style};
+
+/// The scatter plot
+///
+/// Plot the points `(x, y)` with the markers
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{extra::math::{Figure, Marker, Scatter}, Canvas};
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys = [2.0, 4.5, 3.0, 5.5, 1.0];
+/// let mut scatter = Scatter::new(&xs, &ys);
+/// scatter.set_marker(Marker::Cross);
+/// let mut figure = Figure::new();
+/// figure.plot(&scatter);
+/// let mut canvas = Canvas::new();
+/// canvas.paint(&figure, 0, 0).unwrap();
+/// canvas.print();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scatter {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    marker: Marker,
+}
+
+impl Scatter {
+    /// Make a scatter plot
+    ///
+    /// If the length of `xs` and `ys` are different, the extra points are ignored
+    pub fn new(xs: &[f64], ys: &[f64]) -> Self {
+        let (xs, ys) = zip(xs, ys).unzip();
+        Self {
+            xs,
+            ys,
+            marker: Marker::Dot,
+        }
+    }
+
+    /// Make a scatter plot from the points
+    pub fn from_points(points: &[(f64, f64)]) -> Self {
+        let (xs, ys) = points.iter().cloned().unzip();
+        Self {
+            xs,
+            ys,
+            marker: Marker::Dot,
+        }
+    }
+
+    /// Set the marker of the points
+    ///
+    /// The default is [`Marker::Dot`](enum.Marker.html#variant.Dot)
+    pub fn set_marker(&mut self, marker: Marker) {
+        self.marker = marker;
+    }
+}
+
+impl Plotable for Scatter {
+    fn plot(&self) -> (Vec<f64>, Vec<f64>) {
+        (self.xs.clone(), self.ys.clone())
+    }
+
+    fn style(&self) -> Style {
+        Style::Marker(self.marker)
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    color::Color,
+    extra::{Camera, Object3D},
+    utils::RsilleErr,
+    Canvas, Paint,
+};
+
+/// The surface plot
+///
+/// Plot `z = f(x, y)` as a mesh in 3D, it's painted by the [`Object3D`](../struct.Object3D.html),
+/// so the camera, the depth buffer and the shading all work on it.
+/// The surface is scaled to fit in *(60, 60, 30)*, and painted with the center on *(x, y)*.
+///
+/// By default, it's a wireframe with the depth buffer, so the lines behind the surface are hidden.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{color::Color, extra::math::Surface, Canvas};
+/// let mut surface = Surface::new(|x, y| (x * x + y * y).sqrt().sin(), (-6, 6), (-6, 6));
+/// surface.set_ramp(&[Color::Blue, Color::Cyan, Color::Green, Color::Yellow, Color::Red]);
+/// let mut canvas = Canvas::new();
+/// canvas.paint(&surface, 0, 0).unwrap();
+/// canvas.print();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Surface {
+    object: Object3D,
+    // the height of every vertex in *[0, 1]*
+    heights: Vec<f64>,
+}
+
+impl Surface {
+    /// Make a surface plot with 20 * 20 grids
+    pub fn new<F, T>(f: F, xrange: (T, T), yrange: (T, T)) -> Self
+    where
+        F: Fn(f64, f64) -> f64,
+        T: Into<f64>,
+    {
+        Self::with_resolution(f, xrange, yrange, (20, 20))
+    }
+
+    /// Make a surface plot with the count of grids on *x* and *y*
+    pub fn with_resolution<F, T>(
+        f: F,
+        xrange: (T, T),
+        yrange: (T, T),
+        resolution: (usize, usize),
+    ) -> Self
+    where
+        F: Fn(f64, f64) -> f64,
+        T: Into<f64>,
+    {
+        let (x0, x1) = (xrange.0.into(), xrange.1.into());
+        let (y0, y1) = (yrange.0.into(), yrange.1.into());
+        let (nx, ny) = (resolution.0.max(1), resolution.1.max(1));
+        let mut zs = Vec::with_capacity((nx + 1) * (ny + 1));
+        for j in 0..=ny {
+            for i in 0..=nx {
+                let x = x0 + (x1 - x0) * i as f64 / nx as f64;
+                let y = y0 + (y1 - y0) * j as f64 / ny as f64;
+                zs.push(f(x, y));
+            }
+        }
+        let (zmin, zmax) = zs
+            .iter()
+            .filter(|z| z.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), &z| {
+                (a.min(z), b.max(z))
+            });
+        let span = if zmax > zmin { zmax - zmin } else { 1.0 };
+        // the invalid values are put on the bottom
+        let heights: Vec<f64> = zs
+            .iter()
+            .map(|&z| {
+                if z.is_finite() {
+                    (z - zmin) / span
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        let mut points = Vec::with_capacity(heights.len());
+        for j in 0..=ny {
+            for i in 0..=nx {
+                let h = heights[j * (nx + 1) + i];
+                points.push((
+                    60.0 * i as f64 / nx as f64 - 30.0,
+                    60.0 * j as f64 / ny as f64 - 30.0,
+                    30.0 * h - 15.0,
+                ));
+            }
+        }
+        let mut faces = Vec::with_capacity(nx * ny);
+        for j in 0..ny {
+            for i in 0..nx {
+                let k = j * (nx + 1) + i;
+                // counterclockwise seen from the top
+                faces.push(vec![k, k + 1, k + nx + 2, k + nx + 1]);
+            }
+        }
+
+        let mut object = Object3D::new();
+        object.add_points(&points);
+        // the indices are always right
+        let _ = object.add_faces(&faces);
+        object.set_z_buffer(true);
+        let mut camera = Camera::new((0.0, -90.0, 60.0), (0.0, 0.0, 0.0));
+        camera.orbit(-30.0, 0.0);
+        object.set_camera(Some(camera));
+        Self { object, heights }
+    }
+
+    /// Color the lines by the height, from the lowest to the highest
+    ///
+    /// Do nothing if the ramp is empty
+    pub fn set_ramp(&mut self, ramp: &[Color]) {
+        if ramp.is_empty() {
+            return;
+        }
+        for (a, b) in self.object.sides() {
+            let h = (self.heights[a] + self.heights[b]) / 2.0;
+            let i = (h * (ramp.len() - 1) as f64).round() as usize;
+            self.object
+                .set_side_colorful((a, b), ramp[i.min(ramp.len() - 1)]);
+        }
+    }
+
+    /// Fill the surface with the shading instead of the wireframe, the default is `false`
+    ///
+    /// Give a look at [`Object3D::set_shading`](../struct.Object3D.html#method.set_shading)
+    pub fn set_shading(&mut self, shading: bool) {
+        self.object.set_shading(shading);
+    }
+
+    /// Return the [`Object3D`](../struct.Object3D.html) of the surface
+    pub fn object(&self) -> &Object3D {
+        &self.object
+    }
+
+    /// Return the mutable [`Object3D`](../struct.Object3D.html),
+    /// like for moving the camera or changing the light
+    pub fn object_mut(&mut self) -> &mut Object3D {
+        &mut self.object
+    }
+}
+
+impl Paint for Surface {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        self.object.paint(canvas, x, y)
+    }
+}
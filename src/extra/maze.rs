@@ -0,0 +1,439 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+use crate::{
+    canvas::Paint,
+    color::Color,
+    utils::{Rng, RsilleErr},
+    Canvas,
+};
+
+/// The maze
+///
+/// Generate a maze and find the way out, every step can be painted,
+/// so it's a good showcase for the animation, just like the [`LifeGame`](struct.LifeGame.html).
+///
+/// Every cell and wall is a dot, so a `width * height` maze is `(2 * width + 1) * (2 * height + 1)` dots.
+/// The *(0, 0)* is the left bottom cell.
+///
+/// ## Example
+///
+/// generate and solve it step by step
+/// ```no_run
+/// use rsille::{extra::{Generator, Maze, Solver}, Animation};
+/// let mut maze = Maze::new(30, 15);
+/// maze.start_generate(Generator::Backtracker);
+/// maze.start_solve(Solver::AStar);
+/// let mut anime = Animation::new();
+/// anime.push(maze, |m| m.update(), (0, 0));
+/// anime.run();
+/// ```
+///
+/// or just paint the result
+/// ```
+/// use rsille::{extra::{Generator, Maze, Solver}, Canvas};
+/// let mut maze = Maze::new(20, 10);
+/// maze.generate(Generator::Prim);
+/// let path = maze.solve(Solver::Bfs).unwrap();
+/// assert_eq!(path.first(), Some(&(0, 0)));
+/// assert_eq!(path.last(), Some(&(19, 9)));
+/// let mut c = Canvas::new();
+/// c.paint(&maze, 0, 0).unwrap();
+/// c.print();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Maze {
+    width: usize,
+    height: usize,
+    // true is the wall, (2 * width + 1) * (2 * height + 1)
+    walls: Vec<bool>,
+    start: (usize, usize),
+    goal: (usize, usize),
+    visited: Vec<bool>,
+    path: Option<Vec<(usize, usize)>>,
+    tasks: VecDeque<Task>,
+    visited_color: Color,
+    path_color: Color,
+    rng: Rng,
+}
+
+/// The algorithm to generate the [`Maze`](struct.Maze.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Generator {
+    /// The recursive backtracker, it makes the long and winding passages
+    Backtracker,
+    /// The randomized Prim's algorithm, it makes many short dead ends
+    Prim,
+}
+
+/// The algorithm to find the way in the [`Maze`](struct.Maze.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solver {
+    /// The breadth first search
+    Bfs,
+    /// The A* search with the manhattan distance
+    AStar,
+}
+
+#[derive(Debug, Clone)]
+enum Task {
+    Generate(Generator, Option<GenState>),
+    Solve(Solver, Option<SolveState>),
+}
+
+#[derive(Debug, Clone)]
+struct GenState {
+    in_maze: Vec<bool>,
+    // the stack of the backtracker
+    stack: Vec<usize>,
+    // the (from, to) of the prim
+    frontier: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+struct SolveState {
+    // Reverse((cost + heuristic, order, cell)), the order makes the same cost first in first out
+    open: BinaryHeap<Reverse<(usize, usize, usize)>>,
+    cost: Vec<usize>,
+    from: Vec<Option<usize>>,
+    order: usize,
+}
+
+impl Maze {
+    /// Make a maze full of walls
+    ///
+    /// * `width` - the count of cells in a row
+    /// * `height` - the count of cells in a column
+    ///
+    /// The start is the left bottom and the goal is the right top
+    pub fn new(width: usize, height: usize) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        Self {
+            width,
+            height,
+            walls: vec![true; (2 * width + 1) * (2 * height + 1)],
+            start: (0, 0),
+            goal: (width - 1, height - 1),
+            visited: vec![false; width * height],
+            path: None,
+            tasks: VecDeque::new(),
+            visited_color: Color::DarkCyan,
+            path_color: Color::Yellow,
+            rng: Rng::new(0x9e37_79b9_7f4a_7c15),
+        }
+    }
+
+    /// Return the `(width, height)` in cells
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Set the seed of the random, the same seed always makes the same maze
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    /// Set the start and the goal cell of the solver
+    ///
+    /// Those out of the maze are moved to the nearest cell
+    pub fn set_endpoints(&mut self, start: (usize, usize), goal: (usize, usize)) {
+        let clamp = |(x, y): (usize, usize)| (x.min(self.width - 1), y.min(self.height - 1));
+        self.start = clamp(start);
+        self.goal = clamp(goal);
+    }
+
+    /// Set the color of the visited cells and the path
+    ///
+    /// The default is dark cyan and yellow
+    pub fn set_colors(&mut self, visited: Color, path: Color) {
+        self.visited_color = visited;
+        self.path_color = path;
+    }
+
+    /// Return if there is a wall between two neighbor cells
+    ///
+    /// Return `true` if they aren't neighbors or out of the maze
+    pub fn is_wall(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        if a.0 >= self.width || a.1 >= self.height || b.0 >= self.width || b.1 >= self.height {
+            return true;
+        }
+        if a.0.abs_diff(b.0) + a.1.abs_diff(b.1) != 1 {
+            return true;
+        }
+        self.walls[self.dot(a.0 + b.0 + 1, a.1 + b.1 + 1)]
+    }
+
+    /// Return the path found by the solver, from the start to the goal
+    pub fn path(&self) -> Option<&Vec<(usize, usize)>> {
+        self.path.as_ref()
+    }
+
+    /// Generate the maze right now
+    ///
+    /// All the tasks not done are dropped
+    pub fn generate(&mut self, generator: Generator) {
+        self.tasks.clear();
+        self.start_generate(generator);
+        while !self.update() {}
+    }
+
+    /// Find the way from the start to the goal right now
+    ///
+    /// All the tasks not done are dropped, return `None` if there isn't a way
+    pub fn solve(&mut self, solver: Solver) -> Option<Vec<(usize, usize)>> {
+        self.tasks.clear();
+        self.start_solve(solver);
+        while !self.update() {}
+        self.path.clone()
+    }
+
+    /// Generate the maze step by step with [`update`](struct.Maze.html#method.update)
+    ///
+    /// The task is queued, it starts after the tasks before it are done
+    pub fn start_generate(&mut self, generator: Generator) {
+        self.tasks.push_back(Task::Generate(generator, None));
+    }
+
+    /// Find the way step by step with [`update`](struct.Maze.html#method.update)
+    ///
+    /// The task is queued, so it can be called right after the [`start_generate`](struct.Maze.html#method.start_generate)
+    pub fn start_solve(&mut self, solver: Solver) {
+        self.tasks.push_back(Task::Solve(solver, None));
+    }
+
+    /// Do one step of the task
+    ///
+    /// Return true if all the tasks are done
+    pub fn update(&mut self) -> bool {
+        let Some(task) = self.tasks.pop_front() else {
+            return true;
+        };
+        let task = match task {
+            Task::Generate(g, state) => {
+                let mut state = state.unwrap_or_else(|| self.init_generate(g));
+                if self.step_generate(g, &mut state) {
+                    None
+                } else {
+                    Some(Task::Generate(g, Some(state)))
+                }
+            }
+            Task::Solve(s, state) => {
+                let mut state = state.unwrap_or_else(|| self.init_solve(s));
+                if self.step_solve(s, &mut state) {
+                    None
+                } else {
+                    Some(Task::Solve(s, Some(state)))
+                }
+            }
+        };
+        if let Some(task) = task {
+            self.tasks.push_front(task);
+        }
+        self.tasks.is_empty()
+    }
+
+    fn init_generate(&mut self, generator: Generator) -> GenState {
+        self.walls.iter_mut().for_each(|w| *w = true);
+        self.visited.iter_mut().for_each(|v| *v = false);
+        self.path = None;
+        let first = self.rng.below(self.width * self.height);
+        let mut state = GenState {
+            in_maze: vec![false; self.width * self.height],
+            stack: Vec::new(),
+            frontier: Vec::new(),
+        };
+        state.in_maze[first] = true;
+        self.open_cell(first);
+        match generator {
+            Generator::Backtracker => state.stack.push(first),
+            Generator::Prim => {
+                for n in self.neighbors(first) {
+                    state.frontier.push((first, n));
+                }
+            }
+        }
+        state
+    }
+
+    // return true if it's done
+    fn step_generate(&mut self, generator: Generator, state: &mut GenState) -> bool {
+        match generator {
+            Generator::Backtracker => {
+                while let Some(&cell) = state.stack.last() {
+                    let next: Vec<usize> = self
+                        .neighbors(cell)
+                        .filter(|&n| !state.in_maze[n])
+                        .collect();
+                    if next.is_empty() {
+                        state.stack.pop();
+                        continue;
+                    }
+                    let n = next[self.rng.below(next.len())];
+                    self.carve(cell, n);
+                    state.in_maze[n] = true;
+                    state.stack.push(n);
+                    return false;
+                }
+                true
+            }
+            Generator::Prim => {
+                while !state.frontier.is_empty() {
+                    let i = self.rng.below(state.frontier.len());
+                    let (from, to) = state.frontier.swap_remove(i);
+                    if state.in_maze[to] {
+                        continue;
+                    }
+                    self.carve(from, to);
+                    state.in_maze[to] = true;
+                    for n in self.neighbors(to) {
+                        if !state.in_maze[n] {
+                            state.frontier.push((to, n));
+                        }
+                    }
+                    return false;
+                }
+                true
+            }
+        }
+    }
+
+    fn init_solve(&mut self, solver: Solver) -> SolveState {
+        self.visited.iter_mut().for_each(|v| *v = false);
+        self.path = None;
+        let start = self.index(self.start);
+        let mut cost = vec![usize::MAX; self.width * self.height];
+        cost[start] = 0;
+        let mut open = BinaryHeap::new();
+        open.push(Reverse((self.heuristic(solver, start), 0, start)));
+        SolveState {
+            open,
+            cost,
+            from: vec![None; self.width * self.height],
+            order: 1,
+        }
+    }
+
+    // return true if it's done
+    fn step_solve(&mut self, solver: Solver, state: &mut SolveState) -> bool {
+        let goal = self.index(self.goal);
+        while let Some(Reverse((_, _, cell))) = state.open.pop() {
+            if self.visited[cell] {
+                continue;
+            }
+            self.visited[cell] = true;
+            if cell == goal {
+                let mut path = vec![cell];
+                while let Some(prev) = state.from[*path.last().unwrap()] {
+                    path.push(prev);
+                }
+                self.path = Some(
+                    path.into_iter()
+                        .rev()
+                        .map(|i| (i % self.width, i / self.width))
+                        .collect(),
+                );
+                return true;
+            }
+            let neighbors: Vec<usize> = self
+                .neighbors(cell)
+                .filter(|&n| !self.is_wall(self.cell(cell), self.cell(n)))
+                .collect();
+            for n in neighbors {
+                let cost = state.cost[cell] + 1;
+                if cost < state.cost[n] {
+                    state.cost[n] = cost;
+                    state.from[n] = Some(cell);
+                    let f = cost + self.heuristic(solver, n);
+                    state.open.push(Reverse((f, state.order, n)));
+                    state.order += 1;
+                }
+            }
+            return false;
+        }
+        true
+    }
+
+    fn heuristic(&self, solver: Solver, cell: usize) -> usize {
+        match solver {
+            Solver::Bfs => 0,
+            Solver::AStar => {
+                let (x, y) = self.cell(cell);
+                x.abs_diff(self.goal.0) + y.abs_diff(self.goal.1)
+            }
+        }
+    }
+
+    fn neighbors(&self, cell: usize) -> impl Iterator<Item = usize> {
+        let (x, y) = self.cell(cell);
+        let (w, h) = (self.width, self.height);
+        [
+            (x > 0).then(|| cell - 1),
+            (x + 1 < w).then(|| cell + 1),
+            (y > 0).then(|| cell - w),
+            (y + 1 < h).then(|| cell + w),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    fn carve(&mut self, a: usize, b: usize) {
+        let ((ax, ay), (bx, by)) = (self.cell(a), self.cell(b));
+        let i = self.dot(ax + bx + 1, ay + by + 1);
+        self.walls[i] = false;
+        self.open_cell(b);
+    }
+
+    fn open_cell(&mut self, cell: usize) {
+        let (x, y) = self.cell(cell);
+        let i = self.dot(2 * x + 1, 2 * y + 1);
+        self.walls[i] = false;
+    }
+
+    fn cell(&self, i: usize) -> (usize, usize) {
+        (i % self.width, i / self.width)
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        y * self.width + x
+    }
+
+    fn dot(&self, x: usize, y: usize) -> usize {
+        y * (2 * self.width + 1) + x
+    }
+}
+
+impl Paint for Maze {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        let w = 2 * self.width + 1;
+        for (i, &wall) in self.walls.iter().enumerate() {
+            if wall {
+                canvas.set(x + (i % w) as f64, y + (i / w) as f64);
+            }
+        }
+        for (i, _) in self.visited.iter().enumerate().filter(|(_, &v)| v) {
+            let (cx, cy) = self.cell(i);
+            let (px, py) = (2 * cx + 1, 2 * cy + 1);
+            canvas.set_colorful(x + px as f64, y + py as f64, self.visited_color);
+        }
+        if let Some(path) = &self.path {
+            let mut prev: Option<(usize, usize)> = None;
+            for &(cx, cy) in path {
+                let (px, py) = (2 * cx + 1, 2 * cy + 1);
+                canvas.set_colorful(x + px as f64, y + py as f64, self.path_color);
+                // the passage between two cells
+                if let Some((ax, ay)) = prev {
+                    let (mx, my) = (ax + cx + 1, ay + cy + 1);
+                    canvas.set_colorful(x + mx as f64, y + my as f64, self.path_color);
+                }
+                prev = Some((cx, cy));
+            }
+        }
+        Ok(())
+    }
+}
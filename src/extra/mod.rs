@@ -1,14 +1,34 @@
 //! Some useful things can paint on the canvas
 
+#[cfg(feature = "audio")]
+mod audio;
+mod camera;
+mod fractal;
+mod gauge;
+#[cfg(feature = "img")]
+mod graphics;
 #[cfg(feature = "img")]
 mod imgille;
 mod lifegame;
 pub mod math;
+mod maze;
 mod object3d;
+mod particles;
+mod qrcode;
+mod sprite_engine;
 mod turtle;
 
+#[cfg(feature = "audio")]
+pub use audio::{Audio, AudioView, FftWindow};
+pub use camera::Camera;
+pub use fractal::Fractal;
+pub use gauge::Gauge;
 #[cfg(feature = "img")]
-pub use imgille::Imgille;
-pub use lifegame::LifeGame;
+pub use imgille::{Dither, FitMode, Imgille};
+pub use lifegame::{Boundary, LifeEditor, LifeGame};
+pub use maze::{Generator, Maze, Solver};
 pub use object3d::Object3D;
+pub use particles::{Emitter, Particles};
+pub use qrcode::{DrawQr, EcLevel, QrCode};
+pub use sprite_engine::{Input, SpriteEngine, SpriteSheet};
 pub use turtle::Turtle;
@@ -1,11 +1,12 @@
+use super::{math::glm::Quat, Camera};
 use crate::{
     canvas::Paint,
-    utils::{check_zoom, mean, RsilleErr, MIN_DIFFERENCE},
+    utils::{check_zoom, mean, ErrKind, RsilleErr, MIN_DIFFERENCE},
     Canvas,
 };
 
 use crate::color::Color;
-use std::collections::HashMap;
+use std::{collections::HashMap, fs};
 
 /// A paintable Object in 3D
 ///
@@ -32,6 +33,13 @@ pub struct Object3D {
     zoomed_vertices: Option<Vec<Point3D>>,
     center: Point3D,
     sides: HashMap<(usize, usize), Color>,
+    faces: Vec<Vec<usize>>,
+    culling: bool,
+    z_buffer: bool,
+    shading: bool,
+    light: (f64, f64, f64),
+    ramp: Vec<Color>,
+    camera: Option<Camera>,
 }
 
 impl Object3D {
@@ -42,6 +50,13 @@ impl Object3D {
             zoomed_vertices: None,
             center: Point3D::new(0.0, 0.0, 0.0),
             sides: HashMap::new(),
+            faces: Vec::new(),
+            culling: false,
+            z_buffer: false,
+            shading: false,
+            light: (-1.0, 1.0, -1.0),
+            ramp: gray_ramp(),
+            camera: None,
         }
     }
 
@@ -73,7 +88,7 @@ impl Object3D {
         let vn = self.origin_vertices.len();
         for side in sides {
             if vn <= side.0 || vn <= side.1 {
-                return Err(RsilleErr::new("wrong add sides!".to_string()));
+                return Err(out_of_bounds(side.0.max(side.1), vn));
             }
 
             self.sides.insert(*side, Color::Reset);
@@ -91,13 +106,169 @@ impl Object3D {
         let vn = self.origin_vertices.len();
         for (side, color) in sides {
             if vn <= side.0 || vn <= side.1 {
-                return Err(RsilleErr::new("wrong add sides!".to_string()));
+                return Err(out_of_bounds(side.0.max(side.1), vn));
             }
             self.sides.insert(*side, *color);
         }
         Ok(())
     }
 
+    /// Return the faces
+    pub fn faces(&self) -> Vec<Vec<usize>> {
+        self.faces.clone()
+    }
+
+    /// Add faces to object
+    ///
+    /// Every face is the indices of its vertices in counterclockwise order (seen from outside),
+    /// and the edges of those faces are added as sides too.
+    ///
+    /// Return an error if the index is out of range or the face has less than 3 vertices,
+    /// the kind is [`ErrKind::OutOfBounds`](crate::ErrKind::OutOfBounds) for both
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{extra::Object3D, ErrKind};
+    /// let mut obj = Object3D::new();
+    /// obj.add_points(&[(0.0, 0.0, 0.0), (1.0, 0.0, 0.0), (0.0, 1.0, 0.0)]);
+    /// assert!(obj.add_faces(&[vec![0, 1, 2]]).is_ok());
+    /// let err = obj.add_faces(&[vec![0, 1]]).unwrap_err();
+    /// assert_eq!(err.kind(), &ErrKind::OutOfBounds { requested: 2, available: 3 });
+    /// ```
+    pub fn add_faces(&mut self, faces: &[Vec<usize>]) -> Result<(), RsilleErr> {
+        let vn = self.origin_vertices.len();
+        for face in faces {
+            if face.len() < 3 {
+                return Err(RsilleErr::with_kind(
+                    ErrKind::OutOfBounds {
+                        requested: face.len(),
+                        available: 3,
+                    },
+                    format!("a face needs 3 vertices at least, but got {}", face.len()),
+                ));
+            }
+            if let Some(&i) = face.iter().find(|&&i| i >= vn) {
+                return Err(out_of_bounds(i, vn));
+            }
+        }
+        for face in faces {
+            for (i, &a) in face.iter().enumerate() {
+                let b = face[(i + 1) % face.len()];
+                if !self.sides.contains_key(&(b, a)) {
+                    self.sides.entry((a, b)).or_insert(Color::Reset);
+                }
+            }
+            self.faces.push(face.clone());
+        }
+        Ok(())
+    }
+
+    /// Return the unit normals of the faces
+    ///
+    /// The normals are in the same order of [`faces`](struct.Object3D.html#method.faces)
+    pub fn normals(&self) -> Vec<(f64, f64, f64)> {
+        let points = self.points();
+        self.faces
+            .iter()
+            .map(|face| normal(face.iter().map(|&i| points[i])).get())
+            .collect()
+    }
+
+    /// Move the object to the origin and scale it to fit in a cube
+    ///
+    /// * `size` - the side length of the cube
+    ///
+    /// It's useful for those loaded models, their size and location are unknown
+    pub fn normalize(&mut self, size: f64) {
+        if self.origin_vertices.is_empty() {
+            return;
+        }
+        let (mut min, mut max) = ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]);
+        for p in &self.origin_vertices {
+            for (i, v) in [p.x, p.y, p.z].into_iter().enumerate() {
+                min[i] = min[i].min(v);
+                max[i] = max[i].max(v);
+            }
+        }
+        let extent = (0..3).map(|i| max[i] - min[i]).fold(0.0, f64::max);
+        let factor = if extent > MIN_DIFFERENCE {
+            size / extent
+        } else {
+            1.0
+        };
+        let mid: Vec<f64> = (0..3).map(|i| (min[i] + max[i]) / 2.0).collect();
+        for p in &mut self.origin_vertices {
+            p.x = (p.x - mid[0]) * factor;
+            p.y = (p.y - mid[1]) * factor;
+            p.z = (p.z - mid[2]) * factor;
+        }
+        self.zoomed_vertices = None;
+        self.center = Point3D::new(0.0, 0.0, 0.0);
+    }
+
+    /// Don't paint the faces facing away from the viewer, the default is `false`
+    ///
+    /// The viewer is looking along the `+y`, so the face is culled when its normal has `y >= 0`.
+    /// Those sides only belong to the culled faces are hidden too, and the sides not belong to any face are always painted.
+    /// It needs the faces in counterclockwise order, take a look at [`add_faces`](struct.Object3D.html#method.add_faces)
+    pub fn set_culling(&mut self, culling: bool) {
+        self.culling = culling;
+    }
+
+    /// Use the depth buffer or not, the default is `false`
+    ///
+    /// With the depth buffer, the sides hidden behind the faces won't be painted.
+    /// It makes the dense models readable.
+    pub fn set_z_buffer(&mut self, z_buffer: bool) {
+        self.z_buffer = z_buffer;
+    }
+
+    /// Fill the faces with the flat shading or not, the default is `false`
+    ///
+    /// Every face is filled with a color from the ramp by the Lambert's cosine law,
+    /// the shading always uses the depth buffer.
+    /// Take a look at [`set_light`](struct.Object3D.html#method.set_light) and [`set_ramp`](struct.Object3D.html#method.set_ramp)
+    pub fn set_shading(&mut self, shading: bool) {
+        self.shading = shading;
+    }
+
+    /// Set the direction of the light, the default is *(-1, 1, -1)*
+    ///
+    /// It's the direction the light goes, not the position of the light
+    pub fn set_light(&mut self, direction: (f64, f64, f64)) {
+        self.light = direction;
+    }
+
+    /// Set the color ramp for the shading, from the darkest to the brightest
+    ///
+    /// The default is 8 levels of gray, do nothing if the ramp is empty
+    pub fn set_ramp(&mut self, ramp: &[Color]) {
+        if !ramp.is_empty() {
+            self.ramp = ramp.to_vec();
+        }
+    }
+
+    /// Set the camera, `None` to use the orthographic projection
+    ///
+    /// The default is `None`, the object is projected to the *xz* plane and the viewer is looking along the `+y`.
+    /// With a [`Camera`](struct.Camera.html), the object is painted in perspective,
+    /// and the culling, depth buffer and shading all work in the view of the camera.
+    /// The *(x, y)* of [`paint`](../trait.Paint.html#tymethod.paint) is the center of the view.
+    pub fn set_camera(&mut self, camera: Option<Camera>) {
+        self.camera = camera;
+    }
+
+    /// Return the camera
+    pub fn camera(&self) -> Option<&Camera> {
+        self.camera.as_ref()
+    }
+
+    /// Return the mutable camera, so it can be moved between frames
+    pub fn camera_mut(&mut self) -> Option<&mut Camera> {
+        self.camera.as_mut()
+    }
+
     /// Set the color of the side
     ///
     /// If there isn't the side, it will do nothing
@@ -112,8 +283,25 @@ impl Object3D {
     /// Normaly, the rotate won't grow the error of f64.
     /// If the error is growing, use [`rotate_new`](struct.Object3D.html#method.rotate_new)
     pub fn rotate(&mut self, angle: (f64, f64, f64)) {
+        self.rotate_quat(&Quat::from_euler(angle));
+    }
+
+    /// Rotate the whole object by the quaternion
+    ///
+    /// It's easy to rotate about any axis or between two rotations smoothly,
+    /// take a look at [`Quat`](math/glm/struct.Quat.html)
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::extra::{math::glm::Quat, Object3D};
+    /// let mut cube = Object3D::cube(30.0);
+    /// // about the diagonal of the cube
+    /// cube.rotate_quat(&Quat::from_axis_angle((1.0, 1.0, 1.0), 30.0));
+    /// ```
+    pub fn rotate_quat(&mut self, q: &Quat) {
         for p in &mut self.origin_vertices {
-            p.rotate(angle);
+            *p = Point3D::from(q.rotate(p.get()));
         }
     }
 
@@ -156,6 +344,13 @@ impl Object3D {
             zoomed_vertices: None,
             sides: self.sides.clone(),
             center: self.center,
+            faces: self.faces.clone(),
+            culling: self.culling,
+            z_buffer: self.z_buffer,
+            shading: self.shading,
+            light: self.light,
+            ramp: self.ramp.clone(),
+            camera: self.camera,
         }
     }
 
@@ -172,6 +367,14 @@ impl Object3D {
         }
     }
 
+    fn points(&self) -> &Vec<Point3D> {
+        if let Some(p) = &self.zoomed_vertices {
+            p
+        } else {
+            &self.origin_vertices
+        }
+    }
+
     fn calc_center(&mut self) {
         let (mut xs, mut ys, mut zs) = (Vec::new(), Vec::new(), Vec::new());
         for p in &self.origin_vertices {
@@ -201,23 +404,212 @@ impl Paint for Object3D {
         T: Into<f64>,
     {
         let (x, y) = (x.into(), y.into());
-        let points = if let Some(p) = &self.zoomed_vertices {
-            p
-        } else {
-            &self.origin_vertices
+        // only the dots on the canvas are drawn, the vertex near the eye may be projected very far
+        let (x0, y0, x1, y1) = canvas.shown_area();
+        let clip = (x0 - x, y0 - y, x1 - x, y1 - y);
+        let world = self.points();
+        // (x, depth, y) on the screen, None if it's clipped by the camera
+        let points: Vec<Option<Point3D>> = match &self.camera {
+            Some(camera) => camera
+                .project(world.iter().map(|p| p.get()))
+                .into_iter()
+                .map(|p| p.map(Point3D::from))
+                .collect(),
+            None => world.iter().map(|&p| Some(p)).collect(),
         };
 
+        if !self.culling && !self.z_buffer && !self.shading {
+            for (side, color) in &self.sides {
+                let (Some(v1), Some(v2)) = (points[side.0], points[side.1]) else {
+                    continue;
+                };
+                let Some((v1, v2)) = clip_side(v1, v2, clip) else {
+                    continue;
+                };
+                let xy1 = (x + v1.x, y + v1.z);
+                let xy2 = (x + v2.x, y + v2.z);
+                canvas.line_colorful(xy1, xy2, *color);
+            }
+            return Ok(());
+        }
+
+        // the viewer is looking along +y, so the front faces have the normal with y < 0
+        let visible: Vec<bool> = self
+            .faces
+            .iter()
+            .map(|face| {
+                let Some(screen) = face.iter().map(|&i| points[i]).collect::<Option<Vec<_>>>()
+                else {
+                    return false;
+                };
+                !self.culling || normal(screen.into_iter()).y < 0.0
+            })
+            .collect();
+
+        // (col, row) of dot -> (depth, color of the face)
+        let mut depth: HashMap<(i32, i32), (f64, Option<Color>)> = HashMap::new();
+        if self.z_buffer || self.shading {
+            for (i, face) in self.faces.iter().enumerate() {
+                if !visible[i] {
+                    continue;
+                }
+                let color = if self.shading {
+                    Some(self.shade(normal(face.iter().map(|&i| world[i]))))
+                } else {
+                    None
+                };
+                // the face is a fan of triangles
+                let screen: Vec<Point3D> = face.iter().flat_map(|&i| points[i]).collect();
+                for w in screen[1..].windows(2) {
+                    let tri = [screen[0], w[0], w[1]];
+                    rasterize(tri, clip, |px, py, d| {
+                        let e = depth.entry((px, py)).or_insert((f64::INFINITY, None));
+                        if d < e.0 {
+                            *e = (d, color);
+                        }
+                    });
+                }
+            }
+        }
+        if self.shading {
+            for (&(px, py), &(_, color)) in &depth {
+                if let Some(color) = color {
+                    canvas.set_colorful(x + px as f64, y + py as f64, color);
+                }
+            }
+        }
+
+        // which faces the side belongs to
+        let mut side_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (i, face) in self.faces.iter().enumerate() {
+            for (j, &a) in face.iter().enumerate() {
+                let b = face[(j + 1) % face.len()];
+                side_faces.entry((a.min(b), a.max(b))).or_default().push(i);
+            }
+        }
         for (side, color) in &self.sides {
-            let (v1, v2) = (points[side.0], points[side.1]);
-            let xy1 = (x + v1.x, y + v1.z);
-            let xy2 = (x + v2.x, y + v2.z);
-            canvas.line_colorful(xy1, xy2, *color);
+            let key = (side.0.min(side.1), side.0.max(side.1));
+            if let Some(faces) = side_faces.get(&key) {
+                // the faces are already filled
+                if self.shading || faces.iter().all(|&f| !visible[f]) {
+                    continue;
+                }
+            }
+            let (Some(v1), Some(v2)) = (points[side.0], points[side.1]) else {
+                continue;
+            };
+            let Some((v1, v2)) = clip_side(v1, v2, clip) else {
+                continue;
+            };
+            if !self.z_buffer {
+                canvas.line_colorful((x + v1.x, y + v1.z), (x + v2.x, y + v2.z), *color);
+                continue;
+            }
+            let steps = (v2.x - v1.x).abs().max((v2.z - v1.z).abs()).ceil().max(1.0);
+            for i in 0..=steps as usize {
+                let t = i as f64 / steps;
+                let (px, pz) = (v1.x + (v2.x - v1.x) * t, v1.z + (v2.z - v1.z) * t);
+                let d = v1.y + (v2.y - v1.y) * t;
+                let pos = (px.round() as i32, pz.round() as i32);
+                // a little tolerance, or the side will be hidden by its own face
+                if depth.get(&pos).is_some_and(|&(z, _)| z < d - 1.0) {
+                    continue;
+                }
+                canvas.set_colorful(x + px, y + pz, *color);
+            }
         }
 
         Ok(())
     }
 }
 
+impl Object3D {
+    // the color of the face by the Lambert's cosine law
+    fn shade(&self, normal: Point3D) -> Color {
+        const AMBIENT: f64 = 0.15;
+        let (lx, ly, lz) = self.light;
+        let len = (lx * lx + ly * ly + lz * lz).sqrt().max(MIN_DIFFERENCE);
+        let cos = -(normal.x * lx + normal.y * ly + normal.z * lz) / len;
+        let intensity = AMBIENT + (1.0 - AMBIENT) * cos.max(0.0);
+        let i = (intensity * (self.ramp.len() - 1) as f64).round() as usize;
+        self.ramp[i.min(self.ramp.len() - 1)]
+    }
+}
+
+// call f(col, row, depth) on every dot inside the projected triangle
+// only the dots in the `clip` of (min x, min z, max x, max z) are called
+fn rasterize<F>(tri: [Point3D; 3], clip: (f64, f64, f64, f64), mut f: F)
+where
+    F: FnMut(i32, i32, f64),
+{
+    let [a, b, c] = tri;
+    let area = (b.x - a.x) * (c.z - a.z) - (c.x - a.x) * (b.z - a.z);
+    if area.abs() < MIN_DIFFERENCE {
+        return;
+    }
+    let minx = a.x.min(b.x).min(c.x).max(clip.0).floor();
+    let maxx = a.x.max(b.x).max(c.x).min(clip.2).ceil();
+    let minz = a.z.min(b.z).min(c.z).max(clip.1).floor();
+    let maxz = a.z.max(b.z).max(c.z).min(clip.3).ceil();
+    // out of the canvas
+    if minx > maxx || minz > maxz {
+        return;
+    }
+    let (minx, maxx, minz, maxz) = (minx as i32, maxx as i32, minz as i32, maxz as i32);
+    for pz in minz..=maxz {
+        for px in minx..=maxx {
+            let (px_, pz_) = (px as f64, pz as f64);
+            let w0 = ((b.x - px_) * (c.z - pz_) - (c.x - px_) * (b.z - pz_)) / area;
+            let w1 = ((c.x - px_) * (a.z - pz_) - (a.x - px_) * (c.z - pz_)) / area;
+            let w2 = 1.0 - w0 - w1;
+            if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                continue;
+            }
+            f(px, pz, w0 * a.y + w1 * b.y + w2 * c.y);
+        }
+    }
+}
+
+// cut the side (x, depth, z) by the `clip` of (min x, min z, max x, max z), None if it's all out
+fn clip_side(v1: Point3D, v2: Point3D, clip: (f64, f64, f64, f64)) -> Option<(Point3D, Point3D)> {
+    let (dx, dz) = (v2.x - v1.x, v2.z - v1.z);
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+    // the Liang-Barsky, every (p, q) is an edge of the clip
+    for (p, q) in [
+        (-dx, v1.x - clip.0),
+        (dx, clip.2 - v1.x),
+        (-dz, v1.z - clip.1),
+        (dz, clip.3 - v1.z),
+    ] {
+        if p.abs() < MIN_DIFFERENCE {
+            if q < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let t = q / p;
+        if p < 0.0 {
+            t0 = t0.max(t);
+        } else {
+            t1 = t1.min(t);
+        }
+        if t0 > t1 {
+            return None;
+        }
+    }
+    let at = |t: f64| Point3D::new(v1.x + dx * t, v1.y + (v2.y - v1.y) * t, v1.z + dz * t);
+    Some((at(t0), at(t1)))
+}
+
+fn gray_ramp() -> Vec<Color> {
+    (0..8_u32)
+        .map(|i| {
+            let v = (60 + i * 195 / 7) as u8;
+            Color::Rgb { r: v, g: v, b: v }
+        })
+        .collect()
+}
+
 impl Object3D {
     /// Make a cube
     pub fn cube<T>(side_len: T) -> Object3D
@@ -263,6 +655,203 @@ impl Object3D {
             ])
             .unwrap();
         object
+            .add_faces(&[
+                vec![0, 1, 4, 2],
+                vec![3, 6, 7, 5],
+                vec![0, 3, 5, 1],
+                vec![2, 4, 7, 6],
+                vec![0, 2, 6, 3],
+                vec![1, 5, 7, 4],
+            ])
+            .unwrap();
+        object
+    }
+}
+
+impl Object3D {
+    /// Read the Wavefront `obj` file and build an object from it
+    ///
+    /// Only the vertices and faces are used, the textures, normals and materials are ignored.
+    /// The `obj` is y-up, it's rotated to z-up (the `z` is the up on the screen).
+    ///
+    /// Return `err` when can't open the file or can't parse it
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// use rsille::{extra::Object3D, Animation};
+    /// let mut obj = Object3D::from_obj("path/to/model.obj").unwrap();
+    /// obj.normalize(60.0);
+    /// let mut anime = Animation::new();
+    /// anime.push(obj, |obj| {
+    ///     obj.rotate((0.0, 0.0, 2.0));
+    ///     false
+    /// }, (40, -40));
+    /// anime.run();
+    /// ```
+    pub fn from_obj(path: &str) -> Result<Self, RsilleErr> {
+        let obj = fs::read_to_string(path)
+            .map_err(|e| RsilleErr::from(e).context(format!("can't open obj file: {}", path)))?;
+        Self::from_obj_str(&obj)
+    }
+
+    /// Read the Wavefront `obj` format string and build an object from it
+    ///
+    /// Take a look at [`from_obj`](struct.Object3D.html#method.from_obj)
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{extra::Object3D, Canvas};
+    /// let tetrahedron = "
+    /// v 0 0 0
+    /// v 1 0 0
+    /// v 0 1 0
+    /// v 0 0 1
+    /// f 1 3 2
+    /// f 1 2 4
+    /// f 1 4 3
+    /// f 2 3 4
+    /// ";
+    /// let mut obj = Object3D::from_obj_str(tetrahedron).unwrap();
+    /// obj.normalize(40.0);
+    /// assert_eq!(obj.faces().len(), 4);
+    /// assert_eq!(obj.sides().len(), 6);
+    /// let mut canvas = Canvas::new();
+    /// canvas.paint(&obj, 0, 0).unwrap();
+    /// canvas.print();
+    /// ```
+    pub fn from_obj_str(obj: &str) -> Result<Self, RsilleErr> {
+        let err = |line: &str| {
+            RsilleErr::with_kind(ErrKind::Parse, format!("can't parse obj line: {}", line))
+        };
+        let (mut points, mut faces) = (Vec::new(), Vec::new());
+        for line in obj.lines() {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("v") => {
+                    let v: Vec<f64> = words.take(3).filter_map(|w| w.parse().ok()).collect();
+                    if v.len() != 3 {
+                        return Err(err(line));
+                    }
+                    // y-up to z-up
+                    points.push((v[0], -v[2], v[1]));
+                }
+                Some("f") => {
+                    let mut face = Vec::new();
+                    for w in words {
+                        // v, v/vt, v/vt/vn or v//vn, and the negative is relative to the end
+                        let Some(Ok(i)) = w.split('/').next().map(|i| i.parse::<i64>()) else {
+                            return Err(err(line));
+                        };
+                        let i = if i < 0 {
+                            points.len() as i64 + i
+                        } else {
+                            i - 1
+                        };
+                        if i < 0 {
+                            return Err(err(line));
+                        }
+                        face.push(i as usize);
+                    }
+                    faces.push(face);
+                }
+                _ => (),
+            }
+        }
+        let mut object = Object3D::new();
+        object.add_points(&points);
+        object.add_faces(&faces)?;
+        Ok(object)
+    }
+
+    /// Read the `stl` file (both binary and ascii) and build an object from it
+    ///
+    /// The same vertices in different triangles are merged into one.
+    ///
+    /// Return `err` when can't open the file or can't parse it
+    pub fn from_stl(path: &str) -> Result<Self, RsilleErr> {
+        let stl = fs::read(path)
+            .map_err(|e| RsilleErr::from(e).context(format!("can't open stl file: {}", path)))?;
+        Self::from_stl_bytes(&stl)
+    }
+
+    /// Read the bytes of `stl` and build an object from it
+    ///
+    /// Take a look at [`from_stl`](struct.Object3D.html#method.from_stl)
+    pub fn from_stl_bytes(stl: &[u8]) -> Result<Self, RsilleErr> {
+        let err = || RsilleErr::with_kind(ErrKind::Parse, "can't parse stl".to_string());
+        let mut triangles = Vec::new();
+        // the binary stl may also start with "solid", so check the size first
+        let binary = stl.len() >= 84
+            && stl.len() == 84 + 50 * u32::from_le_bytes(stl[80..84].try_into().unwrap()) as usize;
+        if binary {
+            for tri in stl[84..].chunks_exact(50) {
+                let f = |i: usize| f32::from_le_bytes(tri[i..i + 4].try_into().unwrap()) as f64;
+                // skip the normal, it will be calculated
+                let v = |i: usize| (f(12 + i * 12), f(16 + i * 12), f(20 + i * 12));
+                triangles.push([v(0), v(1), v(2)]);
+            }
+        } else {
+            let text = std::str::from_utf8(stl).map_err(|_| err())?;
+            let mut vertices = Vec::new();
+            for line in text.lines() {
+                let mut words = line.split_whitespace();
+                if words.next() != Some("vertex") {
+                    continue;
+                }
+                let v: Vec<f64> = words.filter_map(|w| w.parse().ok()).collect();
+                if v.len() != 3 {
+                    return Err(err());
+                }
+                vertices.push((v[0], v[1], v[2]));
+            }
+            if vertices.len() % 3 != 0 {
+                return Err(err());
+            }
+            for t in vertices.chunks_exact(3) {
+                triangles.push([t[0], t[1], t[2]]);
+            }
+        }
+
+        let (mut points, mut faces) = (Vec::new(), Vec::new());
+        let mut index: HashMap<[u64; 3], usize> = HashMap::new();
+        for tri in triangles {
+            let mut face = Vec::new();
+            for p in tri {
+                let key = [p.0.to_bits(), p.1.to_bits(), p.2.to_bits()];
+                let i = *index.entry(key).or_insert_with(|| {
+                    points.push(p);
+                    points.len() - 1
+                });
+                face.push(i);
+            }
+            faces.push(face);
+        }
+        let mut object = Object3D::new();
+        object.add_points(&points);
+        object.add_faces(&faces)?;
+        Ok(object)
+    }
+}
+
+// the unit normal of the polygon, by the Newell's method
+fn normal<I>(points: I) -> Point3D
+where
+    I: Iterator<Item = Point3D> + Clone,
+{
+    let (mut nx, mut ny, mut nz) = (0.0, 0.0, 0.0);
+    let next = points.clone().cycle().skip(1);
+    for (a, b) in points.zip(next) {
+        nx += (a.y - b.y) * (a.z + b.z);
+        ny += (a.z - b.z) * (a.x + b.x);
+        nz += (a.x - b.x) * (a.y + b.y);
+    }
+    let len = (nx * nx + ny * ny + nz * nz).sqrt();
+    if len < MIN_DIFFERENCE {
+        Point3D::new(0.0, 0.0, 0.0)
+    } else {
+        Point3D::new(nx / len, ny / len, nz / len)
     }
 }
 
@@ -297,26 +886,9 @@ impl Point3D {
         (self.x, self.y, self.z)
     }
 
-    /// rotate the point, see [wiki] for more information
-    ///
-    /// [wiki]: <https://en.wikipedia.org/wiki/Rotation_matrix>
+    /// rotate the point about the x, y and z axis one by one
     fn rotate(&mut self, angle: (f64, f64, f64)) {
-        self.rotate_x(angle.0);
-        self.rotate_y(angle.1);
-        self.rotate_z(angle.2);
-
-        // let (x, y, z) = (self.x, self.y, self.z);
-        // let (sx, cx) = anlge_x.to_radians().sin_cos();
-        // let (sy, cy) = anlge_y.to_radians().sin_cos();
-        // let (sz, cz) = angle_z.to_radians().sin_cos();
-        // let (t1, t2, t3) = (
-        //     x * cy + y * sx * sy + z * cx * sy,
-        //     y * cx - z * sx,
-        //     y * sx + z * cx,
-        // );
-        // self.x = cz * t1 - sz * t2;
-        // self.y = sz * t1 + cz * t2;
-        // self.z = cz * t3 - sy * x;
+        *self = Self::from(Quat::from_euler(angle).rotate(self.get()));
     }
 
     /// similar to [rotate] but don't change the original point and return the rotated point
@@ -355,28 +927,14 @@ impl Point3D {
             z: dz,
         }
     }
+}
 
-    /// rotate by an angle about the x axis
-    fn rotate_x(&mut self, angle: f64) {
-        let (s, c) = angle.to_radians().sin_cos();
-        let (y, z) = (self.y, self.z);
-        self.y = y * c - z * s;
-        self.z = y * s + z * c;
-    }
-
-    /// rotate by an angle about the y axis
-    fn rotate_y(&mut self, angle: f64) {
-        let (s, c) = angle.to_radians().sin_cos();
-        let (x, z) = (self.x, self.z);
-        self.x = x * c + z * s;
-        self.z = -x * s + z * c;
-    }
-
-    /// rotate by an angle about the z axis
-    fn rotate_z(&mut self, anlge: f64) {
-        let (s, c) = anlge.to_radians().sin_cos();
-        let (x, y) = (self.x, self.y);
-        self.x = x * c - y * s;
-        self.y = x * s + y * c;
-    }
+fn out_of_bounds(index: usize, vertices: usize) -> RsilleErr {
+    RsilleErr::with_kind(
+        ErrKind::OutOfBounds {
+            requested: index,
+            available: vertices,
+        },
+        format!("no vertex {}, there are only {} vertices", index, vertices),
+    )
 }
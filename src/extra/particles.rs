@@ -0,0 +1,272 @@
+use crate::{
+    canvas::Paint,
+    color::{to_rgb, Color},
+    utils::{Rng, RsilleErr},
+    Canvas,
+};
+
+/// The particle system
+///
+/// The particles are born from the [`Emitter`](enum.Emitter.html),
+/// moved by the gravity, wind and drag, and die when they're too old.
+/// The color of the particle can fade from one color to another in its life.
+///
+/// All the time is in second, use [`step`](struct.Particles.html#method.step) to update the system,
+/// so it won't run faster or slower with the different fps.
+///
+/// ## Example
+///
+/// a fountain
+/// ```no_run
+/// use rsille::{color::Color, extra::{Emitter, Particles}, Animation};
+/// let mut fountain = Particles::new();
+/// fountain.set_emitter(Emitter::Line(10.0, 0.0));
+/// fountain.set_rate(200.0);
+/// fountain.set_velocity((40.0, 60.0), 90.0, 15.0);
+/// fountain.set_gravity(0.0, -60.0);
+/// fountain.set_lifetime(1.0, 2.0);
+/// fountain.set_fade(Color::Cyan, Color::DarkBlue);
+/// let mut anime = Animation::new();
+/// anime.push(fountain, |f| {
+///     f.step(1.0 / 30.0);
+///     false
+/// }, (40, -40));
+/// anime.run();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Particles {
+    particles: Vec<Particle>,
+    emitter: Emitter,
+    rate: f64,
+    pending: f64,
+    lifetime: (f64, f64),
+    speed: (f64, f64),
+    direction: f64,
+    spread: f64,
+    gravity: (f64, f64),
+    wind: (f64, f64),
+    drag: f64,
+    fade: Option<(Color, Color)>,
+    max: usize,
+    rng: Rng,
+}
+
+/// The shape where the particles are born
+///
+/// All the positions are relative to where the [`Particles`](struct.Particles.html) is painted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Emitter {
+    /// All the particles are born at *(0, 0)*
+    Point,
+    /// Born on the line from *(0, 0)* to *(x, y)*
+    Line(f64, f64),
+    /// Born on the circle with the radius and the center *(0, 0)*
+    Circle(f64),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Particle {
+    x: f64,
+    y: f64,
+    vx: f64,
+    vy: f64,
+    age: f64,
+    life: f64,
+}
+
+impl Particles {
+    /// Make a new particle system
+    ///
+    /// The default is a point emitter with 50 particles per second,
+    /// every particle lives 1 ~ 2 seconds and flies to all directions with 10 ~ 20 dots per second.
+    /// No force and no color.
+    pub fn new() -> Self {
+        Self {
+            particles: Vec::new(),
+            emitter: Emitter::Point,
+            rate: 50.0,
+            pending: 0.0,
+            lifetime: (1.0, 2.0),
+            speed: (10.0, 20.0),
+            direction: 90.0,
+            spread: 180.0,
+            gravity: (0.0, 0.0),
+            wind: (0.0, 0.0),
+            drag: 0.0,
+            fade: None,
+            max: 5000,
+            rng: Rng::new(0x2545_f491_4f6c_dd1d),
+        }
+    }
+
+    /// Set the shape of the emitter
+    pub fn set_emitter(&mut self, emitter: Emitter) {
+        self.emitter = emitter;
+    }
+
+    /// Set how many particles are born in one second, 0 to stop emitting
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate.max(0.0);
+    }
+
+    /// Set the min and max life of the particles in second
+    pub fn set_lifetime(&mut self, min: f64, max: f64) {
+        self.lifetime = (min.min(max), min.max(max));
+    }
+
+    /// Set the velocity of the new particles
+    ///
+    /// * `speed` - the min and max speed, in dots per second
+    /// * `direction` - the degree of the direction, 0 is right and 90 is up
+    /// * `spread` - the particle can deviate from the direction at most this degree
+    pub fn set_velocity(&mut self, speed: (f64, f64), direction: f64, spread: f64) {
+        self.speed = (speed.0.min(speed.1), speed.0.max(speed.1));
+        self.direction = direction;
+        self.spread = spread.abs();
+    }
+
+    /// Set the gravity, it's the acceleration and won't be affected by the drag
+    pub fn set_gravity(&mut self, x: f64, y: f64) {
+        self.gravity = (x, y);
+    }
+
+    /// Set the wind, the particles will be accelerated to the speed of the wind by the drag
+    pub fn set_wind(&mut self, x: f64, y: f64) {
+        self.wind = (x, y);
+    }
+
+    /// Set the drag, 0 means no drag
+    ///
+    /// The particle loses this ratio of its speed relative to the wind in one second
+    pub fn set_drag(&mut self, drag: f64) {
+        self.drag = drag.max(0.0);
+    }
+
+    /// Fade the color of the particles from `from` to `to` in their life
+    pub fn set_fade(&mut self, from: Color, to: Color) {
+        self.fade = Some((from, to));
+    }
+
+    /// Set the max count of the living particles, the default is 5000
+    pub fn set_max(&mut self, max: usize) {
+        self.max = max;
+    }
+
+    /// Set the seed of the random, the same seed always makes the same particles
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    /// Emit `n` particles right now
+    pub fn burst(&mut self, n: usize) {
+        for _ in 0..n {
+            if self.particles.len() >= self.max {
+                break;
+            }
+            let particle = self.spawn();
+            self.particles.push(particle);
+        }
+    }
+
+    /// Update the system by `dt` seconds
+    ///
+    /// Emit the new particles, move all the particles and remove the dead particles
+    pub fn step(&mut self, dt: f64) {
+        if dt <= 0.0 {
+            return;
+        }
+        let (gx, gy) = self.gravity;
+        let (wx, wy) = self.wind;
+        let k = (-self.drag * dt).exp();
+        for p in &mut self.particles {
+            p.vx = wx + (p.vx - wx) * k + gx * dt;
+            p.vy = wy + (p.vy - wy) * k + gy * dt;
+            p.x += p.vx * dt;
+            p.y += p.vy * dt;
+            p.age += dt;
+        }
+        self.particles.retain(|p| p.age < p.life);
+
+        self.pending += self.rate * dt;
+        let n = self.pending.floor();
+        self.pending -= n;
+        self.burst(n as usize);
+    }
+
+    /// Return the count of the living particles
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Return if there isn't any living particle
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    /// Remove all the particles
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.pending = 0.0;
+    }
+
+    fn spawn(&mut self) -> Particle {
+        let (x, y) = match self.emitter {
+            Emitter::Point => (0.0, 0.0),
+            Emitter::Line(x, y) => {
+                let t = self.random();
+                (x * t, y * t)
+            }
+            Emitter::Circle(r) => {
+                let (s, c) = (self.random() * 360.0).to_radians().sin_cos();
+                (r * c, r * s)
+            }
+        };
+        let angle = self.direction + (self.random() * 2.0 - 1.0) * self.spread;
+        let speed = self.range(self.speed);
+        let (s, c) = angle.to_radians().sin_cos();
+        Particle {
+            x,
+            y,
+            vx: speed * c,
+            vy: speed * s,
+            age: 0.0,
+            life: self.range(self.lifetime),
+        }
+    }
+
+    fn range(&mut self, (min, max): (f64, f64)) -> f64 {
+        min + (max - min) * self.random()
+    }
+
+    fn random(&mut self) -> f64 {
+        self.rng.next_f64()
+    }
+}
+
+impl Paint for Particles {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        let Some((from, to)) = self.fade else {
+            canvas.set_all(self.particles.iter().map(|p| (x + p.x, y + p.y)), None);
+            return Ok(());
+        };
+        for p in &self.particles {
+            canvas.set_colorful(x + p.x, y + p.y, lerp(from, to, p.age / p.life));
+        }
+        Ok(())
+    }
+}
+
+fn lerp(from: Color, to: Color, t: f64) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    let (a, b) = (to_rgb(from), to_rgb(to));
+    let f = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    Color::Rgb {
+        r: f(a.0, b.0),
+        g: f(a.1, b.1),
+        b: f(a.2, b.2),
+    }
+}
@@ -0,0 +1,631 @@
+use crate::{
+    canvas::Paint,
+    color::Color,
+    utils::{ErrKind, RsilleErr},
+    Canvas, DotMatrix,
+};
+
+/// The QR code
+///
+/// The text is encoded in the byte mode with the smallest version (1 ~ 40) it fits.
+/// Every module is `scale * scale` dots, and there is the quiet zone of 4 modules around it.
+///
+/// By default, the light modules are drawn as the dots, so it looks right on the dark terminal,
+/// [`invert`](struct.QrCode.html#method.invert) it on the light one.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{extra::QrCode, Canvas};
+/// let qr = QrCode::new("https://github.com/nidhoggfgg/rsille").unwrap();
+/// let mut c = Canvas::new();
+/// c.paint(&qr, 0, 0).unwrap();
+/// c.print();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+    version: usize,
+    size: usize,
+    // true is dark, in the order of rows from the top
+    modules: Vec<bool>,
+    scale: u32,
+    invert: bool,
+    color: Color,
+}
+
+/// The error correction level of the [`QrCode`](struct.QrCode.html)
+///
+/// The higher, the more damage it can recover, but the bigger the code is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcLevel {
+    /// About 7% can be recovered
+    Low,
+    /// About 15% can be recovered, it's the default
+    Medium,
+    /// About 25% can be recovered
+    Quartile,
+    /// About 30% can be recovered
+    High,
+}
+
+impl QrCode {
+    /// Encode the text with the [`EcLevel::Medium`](enum.EcLevel.html)
+    ///
+    /// Return error if the text is too long
+    pub fn new(text: &str) -> Result<Self, RsilleErr> {
+        Self::with_level(text, EcLevel::Medium)
+    }
+
+    /// Encode the text with the error correction level
+    pub fn with_level(text: &str, level: EcLevel) -> Result<Self, RsilleErr> {
+        let data = text.as_bytes();
+        let version = (1..=40)
+            .find(|&v| 4 + count_bits(v) + data.len() * 8 <= data_codewords(v, level) * 8)
+            .ok_or_else(|| {
+                // the mode and the count take 20 bits of the version 40
+                let available = (data_codewords(40, level) * 8 - 4 - count_bits(40)) / 8;
+                let err = RsilleErr::with_kind(
+                    ErrKind::OutOfBounds {
+                        requested: data.len(),
+                        available,
+                    },
+                    format!("the text is too long for qr code: {} bytes", data.len()),
+                );
+                if level == EcLevel::Low {
+                    err
+                } else {
+                    err.with_hint("a lower error correction level holds more text")
+                }
+            })?;
+
+        // the mode, the count and the data
+        let mut bits = Bits::default();
+        bits.push(0b0100, 4);
+        bits.push(data.len() as u32, count_bits(version));
+        for &b in data {
+            bits.push(b as u32, 8);
+        }
+        // the terminator and the padding
+        let capacity = data_codewords(version, level) * 8;
+        bits.push(0, (capacity - bits.len()).min(4));
+        bits.push(0, (8 - bits.len() % 8) % 8);
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if bits.len() >= capacity {
+                break;
+            }
+            bits.push(*pad, 8);
+        }
+
+        let codewords = add_ecc(&bits.bytes(), version, level);
+        let mut qr = Matrix::new(version);
+        qr.draw_function_patterns();
+        qr.draw_codewords(&codewords);
+        // the mask with the lowest penalty
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                let mut q = qr.clone();
+                q.apply_mask(mask);
+                q.draw_format(level, mask);
+                q.penalty()
+            })
+            .unwrap_or(0);
+        qr.apply_mask(mask);
+        qr.draw_format(level, mask);
+
+        Ok(Self {
+            version,
+            size: qr.size,
+            modules: qr.modules,
+            scale: 2,
+            invert: false,
+            color: Color::Reset,
+        })
+    }
+
+    /// Return the version, it's 1 ~ 40
+    pub fn version(&self) -> usize {
+        self.version
+    }
+
+    /// Return the count of modules of one side, without the quiet zone
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Return if the module is dark, *(0, 0)* is the left top corner
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y * self.size + x]
+    }
+
+    /// Set how many dots of one side of a module, the default is 2
+    pub fn set_scale(&mut self, scale: u32) {
+        self.scale = scale.max(1);
+    }
+
+    /// Use the biggest scale to fit in `(cols, rows)` cells of the terminal, at least 1
+    ///
+    /// The `glyphs` tell how many dots in a cell, it's usually the [glyphs](crate::Canvas::glyphs) of the canvas
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{extra::QrCode, Canvas, Glyphs};
+    /// let mut c = Canvas::new();
+    /// c.set_glyphs(Glyphs::HalfBlock);
+    /// let mut qr = QrCode::new("rsille").unwrap();
+    /// qr.fit(80, 40, c.glyphs());
+    /// c.paint(&qr, 0, 0).unwrap();
+    /// c.print();
+    /// ```
+    pub fn fit(&mut self, cols: u32, rows: u32, glyphs: &dyn DotMatrix) {
+        let side = (self.size + 8) as u32;
+        let (w, h) = glyphs.size();
+        self.set_scale((cols * w as u32 / side).min(rows * h as u32 / side));
+    }
+
+    /// Draw the dark modules as the dots or not, the default is `false`
+    pub fn invert(&mut self, invert: bool) {
+        self.invert = invert;
+    }
+
+    /// Set the color
+    pub fn color(&mut self, color: Color) {
+        self.color = color;
+    }
+}
+
+impl Paint for QrCode {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (x.into(), y.into());
+        let s = self.scale as usize;
+        // with the quiet zone
+        let side = self.size + 8;
+        for my in 0..side {
+            for mx in 0..side {
+                let dark = mx >= 4 && my >= 4 && self.get(mx - 4, my - 4);
+                if dark != self.invert {
+                    continue;
+                }
+                for dy in 0..s {
+                    for dx in 0..s {
+                        // the y of the canvas is facing up
+                        let (px, py) = (mx * s + dx, (side - 1 - my) * s + dy);
+                        canvas.set_colorful(x + px as f64, y + py as f64, self.color);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Draw the QR code on the canvas directly
+///
+/// It's implemented for the [`Canvas`](crate::Canvas), import it to use the `draw_qr`.
+pub trait DrawQr {
+    /// Draw the QR code of the text on the location (x, y)
+    ///
+    /// It's the short hand of [`QrCode::new`](struct.QrCode.html#method.new) and painting it,
+    /// make the [`QrCode`](struct.QrCode.html) for the other options.
+    /// The half block [glyphs](crate::Canvas::set_glyphs) make the modules square.
+    ///
+    /// Return error if the text is too long
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{extra::DrawQr, Canvas, Glyphs};
+    /// let mut c = Canvas::new();
+    /// c.set_glyphs(Glyphs::HalfBlock);
+    /// c.draw_qr("https://github.com/nidhoggfgg/rsille", 0, 0).unwrap();
+    /// c.print();
+    /// ```
+    fn draw_qr<T>(&mut self, text: &str, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>;
+}
+
+impl DrawQr for Canvas {
+    fn draw_qr<T>(&mut self, text: &str, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        self.paint(&QrCode::new(text)?, x, y)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Bits(Vec<bool>);
+
+impl Bits {
+    fn push(&mut self, v: u32, n: usize) {
+        for i in (0..n).rev() {
+            self.0.push((v >> i) & 1 == 1);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.0
+            .chunks(8)
+            .map(|c| c.iter().fold(0, |b, &bit| b << 1 | bit as u8))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Matrix {
+    size: usize,
+    modules: Vec<bool>,
+    // the finder, timing and those can't be masked
+    function: Vec<bool>,
+    version: usize,
+}
+
+impl Matrix {
+    fn new(version: usize) -> Self {
+        let size = version * 4 + 17;
+        Self {
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+            version,
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.function[y * self.size + x] = true;
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn draw_function_patterns(&mut self) {
+        let size = self.size;
+        for i in 0..size {
+            self.set(6, i, i % 2 == 0);
+            self.set(i, 6, i % 2 == 0);
+        }
+        for (cx, cy) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            self.draw_square(cx, cy, 4, |d| d != 2 && d != 4);
+        }
+        let align = alignment_positions(self.version);
+        let n = align.len();
+        for (i, &ay) in align.iter().enumerate() {
+            for (j, &ax) in align.iter().enumerate() {
+                // those on the finders, all the corners except the right bottom one
+                let edge = |k: usize| k == 0 || k == n - 1;
+                if edge(i) && edge(j) && (i, j) != (n - 1, n - 1) {
+                    continue;
+                }
+                self.draw_square(ax, ay, 2, |d| d != 1);
+            }
+        }
+        // reserve the format area, the real one is drawn after the mask
+        self.draw_format(EcLevel::Medium, 0);
+        self.draw_version();
+    }
+
+    // the square in the `radius` around the center, dark or not by the distance
+    fn draw_square(&mut self, cx: usize, cy: usize, radius: i32, dark: impl Fn(i32) -> bool) {
+        for dy in -radius..=radius {
+            for dx in -radius..=radius {
+                let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+                if x < 0 || y < 0 || x >= self.size as i32 || y >= self.size as i32 {
+                    continue;
+                }
+                self.set(x as usize, y as usize, dark(dx.abs().max(dy.abs())));
+            }
+        }
+    }
+
+    fn draw_format(&mut self, level: EcLevel, mask: usize) {
+        let data = level.format_bits() << 3 | mask as u32;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = (data << 10 | rem) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+
+        let size = self.size;
+        for i in 0..6 {
+            self.set(8, i, bit(i));
+        }
+        self.set(8, 7, bit(6));
+        self.set(8, 8, bit(7));
+        self.set(7, 8, bit(8));
+        for i in 9..15 {
+            self.set(14 - i, 8, bit(i));
+        }
+        for i in 0..8 {
+            self.set(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set(8, size - 15 + i, bit(i));
+        }
+        // always dark
+        self.set(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let mut rem = self.version as u32;
+        for _ in 0..12 {
+            rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+        }
+        let bits = (self.version as u32) << 12 | rem;
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 == 1;
+            let (a, b) = (self.size - 11 + i % 3, i / 3);
+            self.set(a, b, dark);
+            self.set(b, a, dark);
+        }
+    }
+
+    // put the codewords in the zigzag from the right bottom corner
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let size = self.size;
+        let mut i = 0;
+        let mut right = size - 1;
+        loop {
+            // skip the vertical timing pattern
+            if right == 6 {
+                right = 5;
+            }
+            for vert in 0..size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vert } else { vert };
+                    if !self.function[y * size + x] && i < data.len() * 8 {
+                        self.modules[y * size + x] = (data[i >> 3] >> (7 - (i & 7))) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: usize) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let i = y * self.size + x;
+                if invert && !self.function[i] {
+                    self.modules[i] = !self.modules[i];
+                }
+            }
+        }
+    }
+
+    // the lower, the easier to scan
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+        let lines = (0..size).flat_map(|i| {
+            [
+                (0..size).map(|j| self.get(j, i)).collect::<Vec<_>>(),
+                (0..size).map(|j| self.get(i, j)).collect::<Vec<_>>(),
+            ]
+        });
+        for line in lines {
+            // the long runs of the same color
+            let mut run = 1;
+            for k in 1..=size {
+                if k < size && line[k] == line[k - 1] {
+                    run += 1;
+                    continue;
+                }
+                if run >= 5 {
+                    penalty += run - 2;
+                }
+                run = 1;
+            }
+            // those look like the finder
+            for w in line.windows(11) {
+                let finder = [true, false, true, true, true, false, true];
+                if (w[..7] == finder && w[7..].iter().all(|&d| !d))
+                    || (w[4..] == finder && w[..4].iter().all(|&d| !d))
+                {
+                    penalty += 40;
+                }
+            }
+        }
+        // the 2 * 2 blocks
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let c = self.get(x, y);
+                if c == self.get(x + 1, y) && c == self.get(x, y + 1) && c == self.get(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        // the balance of dark and light
+        let dark = self.modules.iter().filter(|&&d| d).count();
+        let total = size * size;
+        let k = (dark * 20)
+            .abs_diff(total * 10)
+            .div_ceil(total)
+            .saturating_sub(1);
+        penalty + k * 10
+    }
+}
+
+impl EcLevel {
+    fn format_bits(&self) -> u32 {
+        match self {
+            EcLevel::Low => 1,
+            EcLevel::Medium => 0,
+            EcLevel::Quartile => 3,
+            EcLevel::High => 2,
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            EcLevel::Low => 0,
+            EcLevel::Medium => 1,
+            EcLevel::Quartile => 2,
+            EcLevel::High => 3,
+        }
+    }
+}
+
+#[rustfmt::skip]
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28],
+    [0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+];
+
+#[rustfmt::skip]
+const ECC_BLOCKS: [[u8; 41]; 4] = [
+    [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25],
+    [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49],
+    [0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68],
+    [0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81],
+];
+
+// the bits for the count of bytes
+fn count_bits(version: usize) -> usize {
+    if version < 10 {
+        8
+    } else {
+        16
+    }
+}
+
+// the count of modules for the data and the ecc
+fn raw_modules(version: usize) -> usize {
+    let mut n = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let align = version / 7 + 2;
+        n -= (25 * align - 10) * align - 55;
+        if version >= 7 {
+            n -= 36;
+        }
+    }
+    n
+}
+
+fn data_codewords(version: usize, level: EcLevel) -> usize {
+    let l = level.index();
+    raw_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[l][version] as usize * ECC_BLOCKS[l][version] as usize
+}
+
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let n = version / 7 + 2;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + n * 2 + 1) / (n * 2 - 2) * 2
+    };
+    let last = version * 4 + 10;
+    let mut pos: Vec<usize> = (0..n - 1).map(|i| last - i * step).collect();
+    pos.push(6);
+    pos.reverse();
+    pos
+}
+
+// split the data into blocks, add the ecc for every block and interleave them
+fn add_ecc(data: &[u8], version: usize, level: EcLevel) -> Vec<u8> {
+    let l = level.index();
+    let blocks = ECC_BLOCKS[l][version] as usize;
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[l][version] as usize;
+    let raw = raw_modules(version) / 8;
+    let short_blocks = blocks - raw % blocks;
+    let short_len = raw / blocks;
+
+    let divisor = rs_divisor(ecc_len);
+    let mut all = Vec::with_capacity(blocks);
+    let mut k = 0;
+    for i in 0..blocks {
+        let len = short_len - ecc_len + usize::from(i >= short_blocks);
+        let mut block = data[k..k + len].to_vec();
+        k += len;
+        let ecc = rs_remainder(&block, &divisor);
+        // a placeholder, so all the blocks are the same length
+        if i < short_blocks {
+            block.push(0);
+        }
+        block.extend(ecc);
+        all.push(block);
+    }
+    let mut result = Vec::with_capacity(raw);
+    for i in 0..all[0].len() {
+        for (j, block) in all.iter().enumerate() {
+            if i != short_len - ecc_len || j >= short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_mul(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_mul(root, 0x02);
+    }
+    result
+}
+
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for &b in data {
+        let factor = b ^ result.remove(0);
+        result.push(0);
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_mul(d, factor);
+        }
+    }
+    result
+}
+
+// the multiplication in GF(2^8) with the polynomial 0x11D
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
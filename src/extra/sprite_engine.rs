@@ -0,0 +1,268 @@
+use std::{
+    io::Write,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
+
+use crate::{motion, term, utils::RsilleErr, Canvas, Sprite};
+
+// the most ticks to catch up before painting, or a slow terminal never gets painted
+const MAX_CATCH_UP: u32 = 5;
+
+/// The frames cut from a big sprite
+///
+/// The frames are from left to right, then from top to bottom, like the most sprite sheets.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::{extra::SpriteSheet, Sprite};
+/// let sheet = SpriteSheet::new(&Sprite::from_braille("⣿⠀⡇\n⠉⠉⠀"), 2, 4);
+/// assert_eq!(sheet.len(), 6);
+/// assert_eq!(sheet.frame(2).unwrap(), &Sprite::from_braille("⡇"));
+/// // the index is wrapped, so it's easy to loop the frames
+/// assert_eq!(sheet.frame(8), sheet.frame(2));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteSheet {
+    frames: Vec<Sprite>,
+}
+
+impl SpriteSheet {
+    /// Cut the sheet into frames of *(width, height)* dots, the rest of the edges are dropped
+    pub fn new(sheet: &Sprite, width: usize, height: usize) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let (cols, rows) = (sheet.width() / width, sheet.height() / height);
+        let mut frames = Vec::with_capacity(cols * rows);
+        for row in 0..rows {
+            // the y of the sprite is facing up
+            let bottom = sheet.height() - (row + 1) * height;
+            for col in 0..cols {
+                let mut frame = Sprite::new(width, height);
+                for y in 0..height {
+                    for x in 0..width {
+                        if let Some(color) = sheet.get(col * width + x, bottom + y) {
+                            frame.set_colorful(x, y, color);
+                        }
+                    }
+                }
+                frames.push(frame);
+            }
+        }
+        Self { frames }
+    }
+
+    /// Make a sprite sheet from the frames
+    pub fn from_frames(frames: Vec<Sprite>) -> Self {
+        Self { frames }
+    }
+
+    /// Return the frame, the index is wrapped by the count of frames
+    ///
+    /// Return `None` if there isn't any frame
+    pub fn frame(&self, index: usize) -> Option<&Sprite> {
+        if self.frames.is_empty() {
+            return None;
+        }
+        self.frames.get(index % self.frames.len())
+    }
+
+    /// Return the count of frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Return `true` if there isn't any frame
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// The keys pressed in the last tick
+///
+/// Most terminals don't tell when a key is released,
+/// so holding a key is seen as pressing it again and again by the key repeat.
+#[derive(Debug, Clone, Default)]
+pub struct Input {
+    keys: Vec<KeyEvent>,
+    tick: u64,
+}
+
+impl Input {
+    /// Return `true` if the key is pressed in the last tick
+    pub fn is_pressed(&self, code: KeyCode) -> bool {
+        self.keys.iter().any(|k| k.code == code)
+    }
+
+    /// Return all the keys pressed in the last tick, in the order of pressing
+    pub fn keys(&self) -> &[KeyEvent] {
+        &self.keys
+    }
+
+    /// Return how many ticks before this one, it's *0* for the first tick
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+}
+
+/// The fixed tick loop for the small games
+///
+/// Every tick, the keys are polled, the canvas is cleared, then the game updates and paints itself.
+/// The game logic always runs at the same ticks per second, even the painting is slow:
+/// if it's behind, the game updates some ticks (*5* at most) before painting once.
+///
+/// * `esc` `ctrl+c` - quit
+///
+/// ## Example
+///
+/// move a ship by the arrows, and blink it by the tick
+/// ```no_run
+/// use crossterm::event::KeyCode;
+/// use rsille::{extra::SpriteEngine, Sprite};
+/// let ship = Sprite::from_braille("⢀⣴⡀\n⠛⠛⠛");
+/// let (mut x, mut y) = (0.0, 0.0);
+/// let mut engine = SpriteEngine::new();
+/// engine.set_size(80, 40);
+/// engine.run(|canvas, input| {
+///     if input.is_pressed(KeyCode::Left) { x -= 2.0; }
+///     if input.is_pressed(KeyCode::Right) { x += 2.0; }
+///     if input.is_pressed(KeyCode::Up) { y += 2.0; }
+///     if input.is_pressed(KeyCode::Down) { y -= 2.0; }
+///     if input.tick() % 10 < 8 {
+///         canvas.blit(&ship, x, y);
+///     }
+///     input.is_pressed(KeyCode::Char('q'))
+/// }).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpriteEngine {
+    canvas: Canvas,
+    tps: u32,
+}
+
+impl SpriteEngine {
+    /// Make a new engine with *30* ticks per second
+    pub fn new() -> Self {
+        Self {
+            canvas: Canvas::new(),
+            tps: 30,
+        }
+    }
+
+    /// Set the ticks per second, it can't be higher than [motion::max_fps](crate::motion::max_fps)
+    pub fn set_tps(&mut self, tps: u32) {
+        self.tps = tps.max(1);
+    }
+
+    /// Set the size of the canvas
+    ///
+    /// Give a look at [Canvas::set_size](crate::Canvas::set_size)
+    pub fn set_size<T>(&mut self, width: T, height: T)
+    where
+        T: Into<f64>,
+    {
+        self.canvas.set_size(width, height);
+    }
+
+    /// Return the canvas, like for the [glyphs](crate::Canvas::set_glyphs) or the viewport
+    pub fn canvas_mut(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    /// Run the game on the whole terminal until the `f` returns `true` or quit by the key
+    ///
+    /// The `f` is called every tick with the cleared canvas and the keys,
+    /// the canvas painted by the last call is shown.
+    ///
+    /// Return the error if it can't print to the terminal.
+    /// If the `f` panics, the terminal is restored before the panic message is shown.
+    pub fn run<F>(&mut self, mut f: F) -> Result<(), RsilleErr>
+    where
+        F: FnMut(&mut Canvas, &Input) -> bool,
+    {
+        // restore the terminal before the panic message, or it's messed up by the raw mode
+        let prev_hook = Arc::new(panic::take_hook());
+        let hook = Arc::clone(&prev_hook);
+        panic::set_hook(Box::new(move |info| {
+            term::restore();
+            hook(info);
+        }));
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| self.game_loop(&mut f)));
+        term::clear();
+        term::move_to(0, 0);
+        term::restore();
+        // our hook holds the other one, drop it first
+        drop(panic::take_hook());
+        if let Ok(prev_hook) = Arc::try_unwrap(prev_hook) {
+            panic::set_hook(prev_hook);
+        }
+        // let the panic go on
+        result.unwrap_or_else(|e| panic::resume_unwind(e))
+    }
+
+    fn game_loop<F>(&mut self, f: &mut F) -> Result<(), RsilleErr>
+    where
+        F: FnMut(&mut Canvas, &Input) -> bool,
+    {
+        let tps = motion::max_fps().map_or(self.tps, |max| self.tps.min(max));
+        let duration = Duration::from_secs(1) / tps.max(1);
+        let mut stdout = std::io::stdout();
+        term::clear();
+        term::hide_cursor();
+        terminal::enable_raw_mode()?;
+        let mut input = Input::default();
+        // the first tick is at once
+        let mut lag = duration;
+        let mut last = Instant::now();
+        loop {
+            let now = Instant::now();
+            lag += now - last;
+            last = now;
+            input.keys.clear();
+            while event::poll(Duration::ZERO)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => {
+                        if key.code == KeyCode::Esc
+                            || (key.code == KeyCode::Char('c')
+                                && key.modifiers == KeyModifiers::CONTROL)
+                        {
+                            return Ok(());
+                        }
+                        input.keys.push(key);
+                    }
+                    Event::Resize(_, _) => term::clear(),
+                    _ => (),
+                }
+            }
+            let mut ticks = 0;
+            while lag >= duration && ticks < MAX_CATCH_UP {
+                self.canvas.clear();
+                if f(&mut self.canvas, &input) {
+                    return Ok(());
+                }
+                // the keys are only pressed in the first tick
+                input.keys.clear();
+                input.tick += 1;
+                lag -= duration;
+                ticks += 1;
+            }
+            if lag >= duration {
+                // too far behind, give up the rest
+                lag = Duration::ZERO;
+            }
+            if ticks > 0 {
+                self.canvas.print_at(&mut stdout, 0, 0)?;
+                stdout.flush()?;
+            }
+            thread::sleep(duration - lag);
+        }
+    }
+}
@@ -51,6 +51,31 @@ use crate::color::Color;
 /// ```
 /// The (50, -50) isn't fixed, you can try to paint on other place.
 ///
+/// fill a square and draw many turtles on one canvas,
+/// every turtle is independent, the `goto` and `teleport` are in the coordinate of the canvas
+/// ```
+/// use rsille::{color::Color, extra::Turtle, Canvas};
+/// let mut canvas = Canvas::new();
+/// let mut square = Turtle::new();
+/// square.fillcolor(Color::Yellow);
+/// square.begin_fill();
+/// for _ in 0..4 {
+///     square.forward(30);
+///     square.left(90);
+/// }
+/// square.end_fill();
+/// let mut star = Turtle::new();
+/// star.radians();
+/// star.pensize(2);
+/// for _ in 0..5 {
+///     star.forward(40);
+///     star.right(std::f64::consts::PI * 0.8);
+/// }
+/// canvas.paint(&square, 0, 0).unwrap();
+/// canvas.paint(&star, 40, 20).unwrap();
+/// canvas.print();
+/// ```
+///
 /// ## NOTE:
 ///
 /// There isn't position or heading function,
@@ -63,6 +88,7 @@ pub struct Turtle {
     anime_proc: Option<Vec<Procedure>>,
     anime_step: f64,
     frame_count: usize,
+    full_circle: f64,
 }
 
 impl Turtle {
@@ -73,6 +99,7 @@ impl Turtle {
             anime_proc: None,
             anime_step: 10.0,
             frame_count: 0,
+            full_circle: 360.0,
         }
     }
 
@@ -141,12 +168,13 @@ impl Turtle {
     }
 
     /// Turn turtle right by angle units.
-    /// * `angle` - the degree
+    /// * `angle` - the degree, or the radian after [`radians`](struct.Turtle.html#method.radians)
     pub fn right<T>(&mut self, angle: T)
     where
         T: Into<f64>,
     {
-        self.add_procedure(Procedure::Right(angle.into()));
+        let angle = self.to_degree(angle.into());
+        self.add_procedure(Procedure::Right(angle));
     }
 
     /// alias: [`right`](struct.Turtle.html#method.right)
@@ -158,7 +186,7 @@ impl Turtle {
     }
 
     /// Turn turtle left by angle units.
-    /// * `angle` - the degree
+    /// * `angle` - the degree, or the radian after [`radians`](struct.Turtle.html#method.radians)
     pub fn left<T>(&mut self, angle: T)
     where
         T: Into<f64>,
@@ -183,7 +211,7 @@ impl Turtle {
     where
         T: Into<f64>,
     {
-        self.add_procedure(Procedure::Circle(radius.into(), extent.into(), 100));
+        self.circle_with_steps(radius, extent, 100);
     }
 
     /// Draw a circle with given radius.
@@ -197,7 +225,8 @@ impl Turtle {
     where
         T: Into<f64>,
     {
-        self.add_procedure(Procedure::Circle(radius.into(), extent.into(), steps));
+        let extent = self.to_degree(extent.into());
+        self.add_procedure(Procedure::Circle(radius.into(), extent, steps));
     }
 
     /// Move turtle to an absolute position. If the pen is down, draw line.
@@ -237,6 +266,56 @@ impl Turtle {
         self.colorful(color);
     }
 
+    /// Set the width of the pen, the default is 1
+    pub fn pensize<T>(&mut self, width: T)
+    where
+        T: Into<f64>,
+    {
+        self.add_procedure(Procedure::PenSize(width.into()));
+    }
+
+    /// alias: [`pensize`](struct.Turtle.html#method.pensize)
+    pub fn width<T>(&mut self, width: T)
+    where
+        T: Into<f64>,
+    {
+        self.pensize(width);
+    }
+
+    /// Set the color to fill the shape, the default is the color of the pen
+    pub fn fillcolor(&mut self, color: Color) {
+        self.add_procedure(Procedure::FillColor(color));
+    }
+
+    /// Start to record the shape to fill
+    ///
+    /// Call it just before drawing the shape,
+    /// and call [`end_fill`](struct.Turtle.html#method.end_fill) after the shape is finished
+    pub fn begin_fill(&mut self) {
+        self.add_procedure(Procedure::BeginFill);
+    }
+
+    /// Fill the shape drawn after the last [`begin_fill`](struct.Turtle.html#method.begin_fill)
+    ///
+    /// The shape is closed automatically, and the self-intersecting part is filled by the even-odd rule
+    pub fn end_fill(&mut self) {
+        self.add_procedure(Procedure::EndFill);
+    }
+
+    /// Use the degree for the angle, it's the default
+    ///
+    /// It only changes the angle of the methods called after it
+    pub fn degrees(&mut self) {
+        self.full_circle = 360.0;
+    }
+
+    /// Use the radian for the angle
+    ///
+    /// It only changes the angle of the methods called after it
+    pub fn radians(&mut self) {
+        self.full_circle = 2.0 * PI;
+    }
+
     /// Build the Turtle for animation
     ///
     /// If you don't need the animation, then don't call this method
@@ -335,6 +414,11 @@ impl Turtle {
     fn add_procedure(&mut self, p: Procedure) {
         self.procedures.push(p);
     }
+
+    // all the angles are recorded in degree
+    fn to_degree(&self, angle: f64) -> f64 {
+        angle * 360.0 / self.full_circle
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -350,6 +434,81 @@ enum Procedure {
     Goto(f64, f64),          // (x, y)
     Circle(f64, f64, usize), // (radius, extent, steps)
     Colorful(Color),
+    PenSize(f64),
+    FillColor(Color),
+    BeginFill,
+    EndFill,
+}
+
+// the state of the pen when painting
+struct Pen {
+    down: bool,
+    color: Color,
+    width: f64,
+    // the vertices of the filling shape
+    fill: Option<Vec<(f64, f64)>>,
+    fill_color: Option<Color>,
+}
+
+impl Pen {
+    fn line(&mut self, canvas: &mut Canvas, from: (f64, f64), to: (f64, f64)) {
+        if let Some(fill) = &mut self.fill {
+            fill.push(to);
+        }
+        if !self.down {
+            return;
+        }
+        if self.width <= 1.0 {
+            canvas.line_colorful(from, to, self.color);
+            return;
+        }
+        // stamp a disk on every dot of the line
+        let r = self.width / 2.0;
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = dx.abs().max(dy.abs()).ceil().max(1.0) as usize;
+        for i in 0..=steps {
+            let t = i as f64 / steps as f64;
+            let (cx, cy) = (from.0 + dx * t, from.1 + dy * t);
+            let ri = r.ceil() as i32;
+            for oy in -ri..=ri {
+                for ox in -ri..=ri {
+                    let (ox, oy) = (ox as f64, oy as f64);
+                    if ox * ox + oy * oy <= r * r {
+                        canvas.set_colorful(cx + ox, cy + oy, self.color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// fill the polygon by the scanline and the even-odd rule
+fn fill_polygon(canvas: &mut Canvas, vertices: &[(f64, f64)], color: Color) {
+    if vertices.len() < 3 {
+        return;
+    }
+    let miny = vertices.iter().map(|v| v.1).fold(f64::INFINITY, f64::min);
+    let maxy = vertices
+        .iter()
+        .map(|v| v.1)
+        .fold(f64::NEG_INFINITY, f64::max);
+    for y in miny.ceil() as i32..=maxy.floor() as i32 {
+        let y = y as f64;
+        let mut xs = Vec::new();
+        for (i, &(x1, y1)) in vertices.iter().enumerate() {
+            let (x2, y2) = vertices[(i + 1) % vertices.len()];
+            // half-open, so the vertex won't be counted twice
+            if (y1 <= y && y < y2) || (y2 <= y && y < y1) {
+                xs.push(x1 + (y - y1) * (x2 - x1) / (y2 - y1));
+            }
+        }
+        xs.sort_by(|a, b| a.total_cmp(b));
+        for pair in xs.chunks_exact(2) {
+            for x in pair[0].ceil() as i32..=pair[1].floor() as i32 {
+                canvas.set_colorful(x as f64, y, color);
+            }
+        }
+    }
 }
 
 fn forward(
@@ -357,15 +516,12 @@ fn forward(
     x: f64,
     y: f64,
     heading: f64,
-    pen: bool,
+    pen: &mut Pen,
     step: f64,
-    color: Color,
 ) -> (f64, f64) {
     let (sr, cr) = heading.to_radians().sin_cos();
     let txy = (x + cr * step, y + sr * step);
-    if pen {
-        canvas.line_colorful((x, y), txy, color);
-    }
+    pen.line(canvas, (x, y), txy);
     txy
 }
 
@@ -377,8 +533,14 @@ impl Paint for Turtle {
         use Procedure::*;
         let (x, y) = (x.into(), y.into());
         let (home_x, home_y) = (x, y);
-        let (mut pen, mut heading, mut x, mut y) = (true, 0.0, x, y);
-        let mut color = Color::Reset;
+        let (mut heading, mut x, mut y) = (0.0, x, y);
+        let mut pen = Pen {
+            down: true,
+            color: Color::Reset,
+            width: 1.0,
+            fill: None,
+            fill_color: None,
+        };
         let procs = if let Some(procs) = &self.anime_proc {
             &procs[0..self.frame_count]
         } else {
@@ -388,13 +550,13 @@ impl Paint for Turtle {
         for p in procs {
             match p {
                 PenDown => {
-                    pen = true;
+                    pen.down = true;
                 }
                 PenUp => {
-                    pen = false;
+                    pen.down = false;
                 }
                 Forward(step) => {
-                    (x, y) = forward(canvas, x, y, heading, pen, *step, color);
+                    (x, y) = forward(canvas, x, y, heading, &mut pen, *step);
                 }
                 Right(angle) => {
                     heading -= angle;
@@ -407,9 +569,7 @@ impl Paint for Turtle {
                     (x, y) = (home_x, home_y);
                 }
                 Goto(tx, ty) => {
-                    if pen {
-                        canvas.line_colorful((x, y), (*tx, *ty), color);
-                    }
+                    pen.line(canvas, (x, y), (*tx, *ty));
                     x = *tx;
                     y = *ty;
                 }
@@ -421,15 +581,28 @@ impl Paint for Turtle {
                             x,
                             y,
                             heading,
-                            pen,
+                            &mut pen,
                             2.0 * radius * (angle / 2.0).to_radians().sin(),
-                            color,
                         );
                         heading -= angle;
                     }
                 }
                 Colorful(c) => {
-                    color = *c;
+                    pen.color = *c;
+                }
+                PenSize(width) => {
+                    pen.width = *width;
+                }
+                FillColor(c) => {
+                    pen.fill_color = Some(*c);
+                }
+                BeginFill => {
+                    pen.fill = Some(vec![(x, y)]);
+                }
+                EndFill => {
+                    if let Some(vertices) = pen.fill.take() {
+                        fill_polygon(canvas, &vertices, pen.fill_color.unwrap_or(pen.color));
+                    }
                 }
             }
         }
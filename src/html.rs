@@ -0,0 +1,130 @@
+use crate::{
+    canvas::WIDE_TAIL,
+    color::{to_rgb, Color, TextStyle, Underline},
+    Canvas,
+};
+
+impl Canvas {
+    /// Return the html of the canvas, it's a `<pre>` with the colored `<span>`
+    ///
+    /// It's the same as what [`print`](struct.Canvas.html#method.print) shows on the terminal,
+    /// so the braille art can be put on the web page, the docs or an `xterm.js` without the terminal.
+    /// The dots without color use the color of the page.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::{color::Color, Canvas};
+    /// let mut c = Canvas::new();
+    /// c.line_colorful((0, 0), (10, 0), Color::Red);
+    /// c.put_text(0, 4, "a <line>", None);
+    /// let html = c.to_html();
+    /// assert!(html.starts_with("<pre class=\"rsille\">"));
+    /// assert!(html.contains("a &lt;line&gt;"));
+    /// assert!(html.contains("color:#"));
+    /// ```
+    pub fn to_html(&self) -> String {
+        let (start_col, start_row) = self.cell_of(self.minx, self.miny);
+        let mut html = String::from("<pre class=\"rsille\">");
+        for row in (start_row..self.height).rev() {
+            // merge the chars with the same style into one span
+            let mut span: Option<(TextStyle, String)> = None;
+            for col in start_col..self.width {
+                let (c, style) = if let Some(text) = self.text.get(&(col, row)) {
+                    if text.char() == WIDE_TAIL {
+                        continue;
+                    }
+                    (text.char(), text.style())
+                } else if let Some(pixel) = self.pixels.get(&(col, row)) {
+                    let style = TextStyle {
+                        foreground: pixel.foreground(),
+                        background: pixel.background(),
+                        ..TextStyle::new()
+                    };
+                    (pixel.pixel().glyph(&*self.glyphs), style)
+                } else {
+                    (self.glyphs.space(), TextStyle::new())
+                };
+                match &mut span {
+                    Some((s, text)) if *s == style => text.push(c),
+                    _ => {
+                        if let Some((s, text)) = span.take() {
+                            push_span(&mut html, &s, &text);
+                        }
+                        span = Some((style, c.to_string()));
+                    }
+                }
+            }
+            if let Some((s, text)) = span {
+                push_span(&mut html, &s, &text);
+            }
+            html.push('\n');
+        }
+        html.push_str("</pre>");
+        html
+    }
+}
+
+fn push_span(html: &mut String, style: &TextStyle, text: &str) {
+    let mut css = Vec::new();
+    if let Some(color) = style.foreground.filter(|&c| c != Color::Reset) {
+        css.push(format!("color:{}", hex(color)));
+    }
+    if let Some(color) = style.background.filter(|&c| c != Color::Reset) {
+        css.push(format!("background-color:{}", hex(color)));
+    }
+    if style.bold {
+        css.push("font-weight:bold".to_string());
+    }
+    if style.italic {
+        css.push("font-style:italic".to_string());
+    }
+    let mut lines = Vec::new();
+    if style.underline.is_some() {
+        lines.push("underline");
+    }
+    if style.strikethrough {
+        lines.push("line-through");
+    }
+    // the style is after all the lines
+    match style.underline {
+        Some(Underline::Double) => lines.push("double"),
+        Some(Underline::Curly) => lines.push("wavy"),
+        _ => (),
+    }
+    if !lines.is_empty() {
+        css.push(format!("text-decoration:{}", lines.join(" ")));
+    }
+    if let Some(color) = style.underline_color.filter(|&c| c != Color::Reset) {
+        css.push(format!("text-decoration-color:{}", hex(color)));
+    }
+    let text = escape(text);
+    if css.is_empty() {
+        html.push_str(&text);
+    } else {
+        html.push_str(&format!(
+            "<span style=\"{}\">{}</span>",
+            css.join(";"),
+            text
+        ));
+    }
+}
+
+fn hex(color: Color) -> String {
+    let (r, g, b) = to_rgb(color);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape(text: &str) -> String {
+    let mut s = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            '&' => s.push_str("&amp;"),
+            '"' => s.push_str("&quot;"),
+            _ => s.push(c),
+        }
+    }
+    s
+}
@@ -0,0 +1,222 @@
+use std::io::Write;
+
+use crate::{
+    document::{decode, encode},
+    term::is_raw_mode,
+    utils::{ErrKind, RsilleErr},
+    Canvas, Paint,
+};
+
+/// The canvas with many layers
+///
+/// Every layer is a [`Canvas`](struct.Canvas.html) with a name,
+/// it can be painted, cleared, hidden and reordered independently.
+/// When printing, all the visible layers are composited into one output,
+/// the upper layer will cover the text of the lower layer and the braille code are mixed.
+///
+/// The first added layer is on the bottom.
+///
+/// ## Example
+///
+/// a life game background and a turtle foreground
+/// ```
+/// use rsille::{extra::{LifeGame, Turtle}, LayeredCanvas};
+/// let mut lc = LayeredCanvas::new();
+/// lc.add_layer("background");
+/// lc.add_layer("foreground");
+/// let lg = LifeGame::from("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+/// let mut t = Turtle::new();
+/// t.forward(30);
+/// lc.paint("background", &lg, 0, 0).unwrap();
+/// lc.paint("foreground", &t, 0, 0).unwrap();
+/// lc.print();
+/// lc.set_visible("background", false);
+/// lc.print();
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayeredCanvas {
+    layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    name: String,
+    canvas: Canvas,
+    visible: bool,
+}
+
+impl LayeredCanvas {
+    /// Make a new layered canvas without any layer
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Add a new empty layer on the top
+    ///
+    /// If the layer is already exists, do nothing
+    pub fn add_layer(&mut self, name: &str) {
+        if self.index(name).is_some() {
+            return;
+        }
+        self.layers.push(Layer {
+            name: name.to_string(),
+            canvas: Canvas::new(),
+            visible: true,
+        });
+    }
+
+    /// Remove the layer and return the canvas of it
+    pub fn remove_layer(&mut self, name: &str) -> Option<Canvas> {
+        let i = self.index(name)?;
+        Some(self.layers.remove(i).canvas)
+    }
+
+    /// Return the names of all layers, from the bottom to the top
+    pub fn names(&self) -> Vec<&str> {
+        self.layers.iter().map(|l| l.name.as_str()).collect()
+    }
+
+    /// Return the canvas of the layer
+    pub fn layer(&self, name: &str) -> Option<&Canvas> {
+        self.layers
+            .iter()
+            .find(|l| l.name == name)
+            .map(|l| &l.canvas)
+    }
+
+    /// Return the mutable canvas of the layer
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut Canvas> {
+        self.layers
+            .iter_mut()
+            .find(|l| l.name == name)
+            .map(|l| &mut l.canvas)
+    }
+
+    /// Paint those [`Paint`](trait.Paint.html) object on the layer at (x, y)
+    ///
+    /// Return `err` when the layer isn't exists
+    pub fn paint<T, N>(&mut self, name: &str, target: &T, x: N, y: N) -> Result<(), RsilleErr>
+    where
+        T: Paint,
+        N: Into<f64>,
+    {
+        let Some(canvas) = self.layer_mut(name) else {
+            return Err(RsilleErr::with_kind(
+                ErrKind::NotFound,
+                format!("no such layer: {}", name),
+            ));
+        };
+        canvas.paint(target, x, y)
+    }
+
+    /// Clear the dots and text on the layer
+    ///
+    /// Take a look at [`Canvas::clear`](struct.Canvas.html#method.clear)
+    pub fn clear(&mut self, name: &str) {
+        if let Some(canvas) = self.layer_mut(name) {
+            canvas.clear();
+        }
+    }
+
+    /// Clear all the layers
+    pub fn clear_all(&mut self) {
+        for layer in &mut self.layers {
+            layer.canvas.clear();
+        }
+    }
+
+    /// Show or hide the layer
+    pub fn set_visible(&mut self, name: &str, visible: bool) {
+        if let Some(i) = self.index(name) {
+            self.layers[i].visible = visible;
+        }
+    }
+
+    /// Return if the layer is visible, `false` if the layer isn't exists
+    pub fn is_visible(&self, name: &str) -> bool {
+        self.index(name).is_some_and(|i| self.layers[i].visible)
+    }
+
+    /// Move the layer to the z-index
+    ///
+    /// The 0 is the bottom, and the index bigger than the count of layers means the top
+    pub fn move_to(&mut self, name: &str, z: usize) {
+        if let Some(i) = self.index(name) {
+            let layer = self.layers.remove(i);
+            let z = z.min(self.layers.len());
+            self.layers.insert(z, layer);
+        }
+    }
+
+    /// Move the layer up by one
+    pub fn raise(&mut self, name: &str) {
+        if let Some(i) = self.index(name) {
+            self.move_to(name, i + 1);
+        }
+    }
+
+    /// Move the layer down by one
+    pub fn lower(&mut self, name: &str) {
+        if let Some(i) = self.index(name) {
+            self.move_to(name, i.saturating_sub(1));
+        }
+    }
+
+    /// Composite all the visible layers into one canvas
+    pub fn composite(&self) -> Canvas {
+        let mut canvas = Canvas::new();
+        // the glyphs of the bottom layer, the others are put into its cells
+        if let Some(bottom) = self.layers.iter().find(|l| l.visible) {
+            canvas.use_glyphs(bottom.canvas.glyphs.clone());
+        }
+        for layer in self.layers.iter().filter(|l| l.visible) {
+            canvas.merge(&layer.canvas);
+        }
+        canvas
+    }
+
+    /// Print all the visible layers to the terminal
+    pub fn print(&self) {
+        let is_raw = is_raw_mode();
+        let mut stdout = std::io::stdout();
+        self.print_on(&mut stdout, is_raw).unwrap();
+    }
+
+    /// Print all the visible layers to the buffer
+    pub fn print_on<W>(&self, w: &mut W, is_raw: bool) -> Result<(), RsilleErr>
+    where
+        W: Write,
+    {
+        self.composite().print_on(w, is_raw)
+    }
+
+    /// Encode all the layers to the bytes of the `.rsille` document
+    ///
+    /// The name, visibility and order of layers are all kept
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(
+            self.layers
+                .iter()
+                .map(|l| (l.name.as_str(), l.visible, &l.canvas)),
+        )
+    }
+
+    /// Decode the layers from the bytes of the `.rsille` document
+    ///
+    /// Return `err` when the bytes isn't a valid document
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, RsilleErr> {
+        let layers = decode(bytes)?
+            .into_iter()
+            .map(|(name, visible, canvas)| Layer {
+                name,
+                canvas,
+                visible,
+            })
+            .collect();
+        Ok(Self { layers })
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|l| l.name == name)
+    }
+}
@@ -103,12 +103,27 @@ mod canvas;
 pub mod color;
 mod decor;
 mod defaults;
+mod document;
+#[cfg(feature = "export")]
+mod export;
 pub mod extra;
+mod html;
+mod layer;
+pub mod motion;
+mod record;
+mod shared;
+mod sprite;
 pub mod term;
 mod utils;
 
-pub use anime::Animation;
+pub use anime::{Animation, Position};
+pub use braille::{DotMatrix, Glyphs};
 pub use canvas::Canvas;
 pub use canvas::Paint;
 pub use decor::Decor;
-pub use utils::RsilleErr;
+pub use layer::LayeredCanvas;
+pub use motion::{Spring, Timeline};
+pub use record::Recorder;
+pub use shared::SharedCanvas;
+pub use sprite::Sprite;
+pub use utils::{ErrKind, RsilleErr};
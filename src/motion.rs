@@ -0,0 +1,331 @@
+//! The values changed in the time and the global settings of the motion
+//!
+//! The [`Spring`](struct.Spring.html) and [`Timeline`](struct.Timeline.html) are also in the root of the crate.
+//!
+//! ## Example
+//!
+//! ```
+//! use rsille::{motion, Spring};
+//! // maybe from the user's config, or it's a slow ssh session
+//! motion::set_reduced_motion(true);
+//! let mut spring = Spring::new(170.0, 26.0);
+//! spring.set_target(100.0);
+//! assert_eq!(spring.step(1.0 / 30.0), 100.0);
+//! # motion::set_reduced_motion(false);
+//! ```
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+static REDUCED_MOTION: AtomicBool = AtomicBool::new(false);
+static MAX_FPS: AtomicU32 = AtomicU32::new(0);
+
+/// Make all the springs and timelines jump to the end right now or not
+///
+/// It's for the people who don't like the motion, and the slow terminals.
+/// Use `ignore_reduced_motion` of them to keep the motion which is really needed.
+pub fn set_reduced_motion(reduced: bool) {
+    REDUCED_MOTION.store(reduced, Ordering::Relaxed);
+}
+
+/// Return if the motion is reduced
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.load(Ordering::Relaxed)
+}
+
+/// Set the max fps of all the [`Animation`](../struct.Animation.html), 0 means no limit
+pub fn set_max_fps(fps: u32) {
+    MAX_FPS.store(fps, Ordering::Relaxed);
+}
+
+/// Return the max fps, `None` if there isn't limit
+pub fn max_fps() -> Option<u32> {
+    match MAX_FPS.load(Ordering::Relaxed) {
+        0 => None,
+        fps => Some(fps),
+    }
+}
+
+// the max time of one step of the spring
+const MAX_STEP: f64 = 1.0 / 240.0;
+
+/// The value moved by a spring
+///
+/// The value is pulled to the target like on a spring, it's more natural than the fixed duration.
+/// Change the target at any time and the value goes on from where it is with its velocity.
+///
+/// All the time is in second, call [`step`](struct.Spring.html#method.step) in every frame.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::Spring;
+/// let mut spring = Spring::new(170.0, 26.0);
+/// spring.set_target(100.0);
+/// while !spring.is_settled() {
+///     spring.step(1.0 / 30.0);
+/// }
+/// assert!((spring.value() - 100.0).abs() < 0.01);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    stiffness: f64,
+    damping: f64,
+    value: f64,
+    velocity: f64,
+    target: f64,
+    precision: f64,
+    ignore_reduced: bool,
+}
+
+impl Spring {
+    /// Make a new spring at *0*
+    ///
+    /// * `stiffness` - how hard the spring pulls, the bigger the faster
+    /// * `damping` - how much the motion is slowed down, the smaller the more bouncy
+    ///
+    /// The *(170, 26)* is a good start, and *(2 * sqrt(stiffness))* damping never bounces.
+    pub fn new(stiffness: f64, damping: f64) -> Self {
+        Self {
+            stiffness: stiffness.max(0.0),
+            damping: damping.max(0.0),
+            value: 0.0,
+            velocity: 0.0,
+            target: 0.0,
+            precision: 0.01,
+            ignore_reduced: false,
+        }
+    }
+
+    /// Set the target, the value will move to it
+    pub fn set_target(&mut self, target: f64) {
+        self.target = target;
+    }
+
+    /// Jump to the value right now and stop
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+        self.velocity = 0.0;
+    }
+
+    /// Set how close to the target is thought as settled, the default is *0.01*
+    pub fn set_precision(&mut self, precision: f64) {
+        self.precision = precision.abs();
+    }
+
+    /// Keep moving even the motion is reduced or not, the default is `false`
+    ///
+    /// Give a look at [`set_reduced_motion`](fn.set_reduced_motion.html)
+    pub fn ignore_reduced_motion(&mut self, ignore: bool) {
+        self.ignore_reduced = ignore;
+    }
+
+    /// Return the current value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Return the current velocity
+    pub fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    /// Return the target
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    /// Return if the value is at the target and stopped
+    pub fn is_settled(&self) -> bool {
+        (self.value - self.target).abs() <= self.precision && self.velocity.abs() <= self.precision
+    }
+
+    /// Update the spring by `dt` seconds and return the new value
+    ///
+    /// When it's settled, the value is snapped to the target
+    pub fn step(&mut self, dt: f64) -> f64 {
+        if dt <= 0.0 {
+            return self.value;
+        }
+        if self.is_settled() || (reduced_motion() && !self.ignore_reduced) {
+            self.set_value(self.target);
+            return self.value;
+        }
+        // small steps, or the stiff spring explodes with the low fps
+        let n = (dt / MAX_STEP).ceil();
+        let h = dt / n;
+        for _ in 0..n as usize {
+            let force = -self.stiffness * (self.value - self.target) - self.damping * self.velocity;
+            self.velocity += force * h;
+            self.value += self.velocity * h;
+        }
+        if self.is_settled() {
+            self.set_value(self.target);
+        }
+        self.value
+    }
+}
+
+/// The value changed by the keyframes
+///
+/// The value goes linearly from one keyframe to the next one.
+/// Use [`then`](struct.Timeline.html#method.then) to put the transitions one by one,
+/// or [`at`](struct.Timeline.html#method.at) to put the keyframe on any time.
+/// The callbacks are called when the time passes them.
+///
+/// Use more timelines for the values changed at the same time.
+///
+/// ## Example
+///
+/// ```
+/// use rsille::Timeline;
+/// let mut x = Timeline::new(0.0);
+/// x.then(1.0, 100.0) // move to 100 in 1 second
+///     .then(0.5, 100.0) // stay 0.5 second
+///     .then(1.0, 0.0) // and go back
+///     .on_end(|| println!("done"));
+/// assert_eq!(x.step(0.5), 50.0);
+/// assert_eq!(x.step(1.0), 100.0);
+/// assert_eq!(x.step(0.5), 50.0);
+/// x.step(10.0);
+/// assert!(x.is_end());
+/// ```
+pub struct Timeline {
+    // sorted by the time
+    keyframes: Vec<(f64, f64)>,
+    callbacks: Vec<(f64, Box<dyn FnMut() + Send>)>,
+    time: f64,
+    repeat: bool,
+    ignore_reduced: bool,
+}
+
+impl Timeline {
+    /// Make a new timeline start with the value
+    pub fn new(value: f64) -> Self {
+        Self {
+            keyframes: vec![(0.0, value)],
+            callbacks: Vec::new(),
+            time: 0.0,
+            repeat: false,
+            ignore_reduced: false,
+        }
+    }
+
+    /// Go to the `value` in `duration` seconds after the last keyframe
+    pub fn then(&mut self, duration: f64, value: f64) -> &mut Self {
+        let t = self.duration() + duration.max(0.0);
+        self.keyframes.push((t, value));
+        self
+    }
+
+    /// Put a keyframe at the `time`, the keyframe at the same time will be replaced
+    pub fn at(&mut self, time: f64, value: f64) -> &mut Self {
+        let time = time.max(0.0);
+        match self.keyframes.binary_search_by(|(t, _)| t.total_cmp(&time)) {
+            Ok(i) => self.keyframes[i].1 = value,
+            Err(i) => self.keyframes.insert(i, (time, value)),
+        }
+        self
+    }
+
+    /// Call `f` when the time passes the `time`
+    pub fn on<F>(&mut self, time: f64, f: F) -> &mut Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.callbacks.push((time.max(0.0), Box::new(f)));
+        self
+    }
+
+    /// Call `f` when the timeline is end, or every time it repeats
+    ///
+    /// The end is the last keyframe when calling this, the keyframes added later won't change it
+    pub fn on_end<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.on(self.duration(), f)
+    }
+
+    /// Start again from the beginning when it's end or not, the default is `false`
+    pub fn set_repeat(&mut self, repeat: bool) {
+        self.repeat = repeat;
+    }
+
+    /// Keep the keyframes even the motion is reduced or not, the default is `false`
+    ///
+    /// When the motion is reduced, the timeline jumps to the end and stays there
+    pub fn ignore_reduced_motion(&mut self, ignore: bool) {
+        self.ignore_reduced = ignore;
+    }
+
+    /// Return the time of the last keyframe
+    pub fn duration(&self) -> f64 {
+        self.keyframes.last().map_or(0.0, |k| k.0)
+    }
+
+    /// Return the current time
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Go back to the beginning, the callbacks won't be called
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+    }
+
+    /// Return if the time passes the last keyframe, always `false` when repeating
+    pub fn is_end(&self) -> bool {
+        !self.repeat && self.time >= self.duration()
+    }
+
+    /// Return the value at the current time
+    pub fn value(&self) -> f64 {
+        self.value_at(self.time)
+    }
+
+    /// Return the value at the `time`
+    pub fn value_at(&self, time: f64) -> f64 {
+        let i = self.keyframes.partition_point(|k| k.0 <= time);
+        if i == 0 {
+            return self.keyframes[0].1;
+        }
+        if i == self.keyframes.len() {
+            return self.keyframes[i - 1].1;
+        }
+        let ((t0, v0), (t1, v1)) = (self.keyframes[i - 1], self.keyframes[i]);
+        v0 + (v1 - v0) * (time - t0) / (t1 - t0)
+    }
+
+    /// Update the timeline by `dt` seconds and return the new value
+    pub fn step(&mut self, dt: f64) -> f64 {
+        let duration = self.duration();
+        let mut from = self.time;
+        let mut left = if reduced_motion() && !self.ignore_reduced {
+            duration - from
+        } else {
+            dt
+        }
+        .max(0.0);
+        loop {
+            let to = (from + left).min(duration);
+            self.fire(from, to);
+            left -= to - from;
+            // the zero duration timeline would never end
+            if !self.repeat || left <= 0.0 || duration <= 0.0 {
+                self.time = to;
+                break;
+            }
+            from = 0.0;
+        }
+        self.value()
+    }
+
+    fn fire(&mut self, from: f64, to: f64) {
+        for (t, f) in &mut self.callbacks {
+            // the callbacks at 0 are called when it starts to run
+            if (from < *t || (*t == 0.0 && from == 0.0 && to > 0.0)) && *t <= to {
+                f();
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+use crate::{utils::RsilleErr, Canvas};
+
+/// Record the frames of the canvas with the time
+///
+/// The record can be saved as the [asciicast v2] (the format of asciinema),
+/// or the gif with the `export` feature, so it's easy to make a demo.
+///
+/// [`Animation`](struct.Animation.html) can record all the frames for you,
+/// give a look at [`Animation::record`](struct.Animation.html#method.record).
+///
+/// ## Example
+///
+/// ```
+/// use std::time::Duration;
+/// use rsille::{Canvas, Recorder};
+/// let mut rec = Recorder::new();
+/// let mut c = Canvas::new();
+/// for i in 0..10 {
+///     c.set(i, 0);
+///     rec.record_at(&c, Duration::from_millis(i as u64 * 100));
+/// }
+/// let cast = rec.to_asciicast();
+/// assert!(cast.starts_with("{\"version\": 2"));
+/// assert_eq!(cast.lines().count(), 11);
+/// ```
+///
+/// [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    frames: Vec<(Duration, Canvas)>,
+    start: Option<Instant>,
+}
+
+impl Recorder {
+    /// Make a new empty recorder
+    pub fn new() -> Self {
+        Self {
+            frames: Vec::new(),
+            start: None,
+        }
+    }
+
+    /// Record the canvas as a frame, the time is from the first frame
+    pub fn record(&mut self, canvas: &Canvas) {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        self.record_at(canvas, start.elapsed());
+    }
+
+    /// Record the canvas as a frame at the `time`
+    ///
+    /// It's for making the same record every time, the time should be increasing
+    pub fn record_at(&mut self, canvas: &Canvas, time: Duration) {
+        self.frames.push((time, canvas.clone()));
+    }
+
+    /// Return the count of frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Return if there isn't any frame
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Remove all the frames
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.start = None;
+    }
+
+    /// Return the asciicast v2 of the frames
+    pub fn to_asciicast(&self) -> String {
+        let (cols, rows) = self.size();
+        let mut cast = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}}}\n",
+            cols.max(1),
+            rows.max(1)
+        );
+        for (time, canvas) in &self.frames {
+            // clear the screen, the frames may be in the different size
+            let mut out = b"\x1b[2J\x1b[H".to_vec();
+            // the raw mode moves to the start of the next line
+            let _ = canvas.print_on(&mut out, true);
+            cast.push_str(&format!(
+                "[{:.6}, \"o\", \"{}\"]\n",
+                time.as_secs_f64(),
+                escape(&String::from_utf8_lossy(&out))
+            ));
+        }
+        cast
+    }
+
+    /// Save the frames as the asciicast v2, play it with `asciinema play`
+    pub fn save_asciicast(&self, path: &str) -> Result<(), RsilleErr> {
+        fs::write(path, self.to_asciicast()).map_err(RsilleErr::from)
+    }
+
+    /// Save the frames as the gif, it repeats forever
+    ///
+    /// * `scale` - every dot will be a `scale * scale` square, like [`save_png`](struct.Canvas.html#method.save_png)
+    ///
+    /// Only available with the `export` feature.
+    #[cfg(feature = "export")]
+    pub fn save_gif(&self, path: &str, scale: u32) -> Result<(), RsilleErr> {
+        use image::{
+            codecs::gif::{GifEncoder, Repeat},
+            imageops, Delay, Frame, Rgba, RgbaImage,
+        };
+
+        use crate::{export::BACKGROUND, utils::ErrKind};
+
+        if self.frames.is_empty() {
+            return Err(RsilleErr::with_kind(
+                ErrKind::NotFound,
+                "no frame to save".to_string(),
+            ));
+        }
+        let file = fs::File::create(path).map_err(RsilleErr::from)?;
+        let mut encoder = GifEncoder::new(file);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(RsilleErr::from)?;
+        // all the frames are the same size as the biggest one, in dots
+        let (width, height) = self.frames.iter().fold((0, 0), |(w, h), (_, canvas)| {
+            let (dw, dh) = canvas.glyphs().size();
+            (
+                w.max(canvas.cols() as u32 * dw as u32),
+                h.max(canvas.rows() as u32 * dh as u32),
+            )
+        });
+        let scale = scale.max(1);
+        let (r, g, b) = BACKGROUND;
+        for (i, (time, canvas)) in self.frames.iter().enumerate() {
+            let mut img =
+                RgbaImage::from_pixel(width * scale, height * scale, Rgba([r, g, b, 255]));
+            imageops::overlay(&mut img, &canvas.to_image(scale), 0, 0);
+            // the last frame is shown 0.1 second
+            let delay = match self.frames.get(i + 1) {
+                Some((next, _)) => next.saturating_sub(*time),
+                None => Duration::from_millis(100),
+            };
+            let frame = Frame::from_parts(img, 0, 0, Delay::from_saturating_duration(delay));
+            encoder.encode_frame(frame).map_err(RsilleErr::from)?;
+        }
+        Ok(())
+    }
+
+    // the max (cols, rows) of all the frames
+    fn size(&self) -> (u16, u16) {
+        self.frames.iter().fold((0, 0), |(c, r), (_, canvas)| {
+            (c.max(canvas.cols()), r.max(canvas.rows()))
+        })
+    }
+}
+
+// escape the string in json
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,132 @@
+use std::{
+    io::Write,
+    sync::{Arc, RwLock},
+};
+
+use crossterm::{cursor::MoveTo, queue};
+
+use crate::{utils::RsilleErr, Canvas, Paint};
+
+/// The canvas shared by the threads
+///
+/// Paint on it in any thread, like the workers of a simulation or the data from the network,
+/// and the render thread only prints the cells changed since the last printing.
+/// It's cheap to clone, and all the clones are the same canvas.
+///
+/// ## Example
+///
+/// ```no_run
+/// use std::{thread, time::Duration};
+/// use rsille::SharedCanvas;
+/// let canvas = SharedCanvas::new();
+/// canvas.write(|c| c.set_size(100, 40));
+/// let worker = canvas.clone();
+/// thread::spawn(move || {
+///     for x in 0..100 {
+///         let x = x as f64;
+///         worker.write(|c| c.set(x, (x / 10.0).sin() * 20.0 + 20.0));
+///         thread::sleep(Duration::from_millis(10));
+///     }
+/// });
+/// let mut stdout = std::io::stdout();
+/// for _ in 0..60 {
+///     canvas.print_at(&mut stdout, 0, 0).unwrap();
+///     thread::sleep(Duration::from_millis(33));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedCanvas {
+    inner: Arc<RwLock<Shared>>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    canvas: Canvas,
+    // the (start_col, start_row, width, height) of the last printing
+    printed: Option<(i32, i32, i32, i32)>,
+}
+
+impl SharedCanvas {
+    /// Make a new empty shared canvas
+    pub fn new() -> Self {
+        Self::from_canvas(Canvas::new())
+    }
+
+    /// Share the canvas
+    pub fn from_canvas(mut canvas: Canvas) -> Self {
+        canvas.track_changes(true);
+        Self {
+            inner: Arc::new(RwLock::new(Shared {
+                canvas,
+                printed: None,
+            })),
+        }
+    }
+
+    /// Change the canvas, other threads wait until it's done
+    pub fn write<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Canvas) -> R,
+    {
+        f(&mut self.inner.write().unwrap().canvas)
+    }
+
+    /// Read the canvas, many threads can read it at the same time
+    pub fn read<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&Canvas) -> R,
+    {
+        f(&self.inner.read().unwrap().canvas)
+    }
+
+    /// Paint the object on the location (x, y), like [`Canvas::paint`](struct.Canvas.html#method.paint)
+    pub fn paint<T, N>(&self, target: &T, x: N, y: N) -> Result<(), RsilleErr>
+    where
+        T: Paint,
+        N: Into<f64>,
+    {
+        self.write(|c| c.paint(target, x, y))
+    }
+
+    /// Return a copy of the canvas
+    pub fn snapshot(&self) -> Canvas {
+        let mut canvas = self.read(|c| c.clone());
+        canvas.track_changes(false);
+        canvas
+    }
+
+    /// Print the changed cells with the left top corner at *(col, row)* of the terminal
+    ///
+    /// All the cells are printed at the first time, or the size of the canvas is changed.
+    pub fn print_at<W>(&self, w: &mut W, col: u16, row: u16) -> Result<(), RsilleErr>
+    where
+        W: Write,
+    {
+        let mut shared = self.inner.write().unwrap();
+        let canvas = &mut shared.canvas;
+        let (start_col, start_row) = canvas.cell_of(canvas.minx, canvas.miny);
+        let size = (start_col, start_row, canvas.width, canvas.height);
+        let changes = canvas.take_changes();
+        if shared.printed != Some(size) {
+            shared.printed = Some(size);
+            return shared.canvas.print_at(w, col, row);
+        }
+        let canvas = &shared.canvas;
+        let print = |w: &mut W| -> std::io::Result<()> {
+            for (c, r) in changes {
+                if c < start_col || c >= canvas.width || r < start_row || r >= canvas.height {
+                    continue;
+                }
+                let x = col as i32 + c - start_col;
+                let y = row as i32 + canvas.height - 1 - r;
+                let (Ok(x), Ok(y)) = (u16::try_from(x), u16::try_from(y)) else {
+                    continue;
+                };
+                queue!(w, MoveTo(x, y))?;
+                canvas.queue_cell(w, c, r)?;
+            }
+            w.flush()
+        };
+        print(w).map_err(RsilleErr::from)
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{
+    color::Color,
+    utils::{make_braille, round, RsilleErr},
+    Canvas, Paint,
+};
+
+/// The prebuilt braille art
+///
+/// A sprite holds the dots and the color of them,
+/// you can build it once and stamp it on the canvas many times.
+/// It's much faster than painting those dots by yourself every frame.
+///
+/// Like the canvas, the *(0, 0)* is the left bottom corner and the `y` is facing up.
+///
+/// ## Example
+///
+/// stamp a small flag three times
+/// ```
+/// use rsille::{color::Color, Canvas, Sprite};
+/// let mut flag = Sprite::from_braille("⣿⣿⣿\n⡇⠀⠀\n⡇⠀⠀");
+/// flag.fill_color(Color::Red);
+/// let mut c = Canvas::new();
+/// c.blit(&flag, 0, 0);
+/// flag.flip_horizontal();
+/// c.blit(&flag, 10, 0);
+/// flag.rotate(1);
+/// c.blit(&flag, 20, 0);
+/// c.print();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sprite {
+    width: usize,
+    height: usize,
+    // None: no dot, Some(Color::Reset): a dot without color
+    dots: Vec<Option<Color>>,
+    transparent: bool,
+}
+
+impl Sprite {
+    /// Return a new empty sprite
+    ///
+    /// * `width` - the width in dots
+    /// * `height` - the height in dots
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            dots: vec![None; width * height],
+            transparent: true,
+        }
+    }
+
+    /// Build a sprite from the braille code
+    ///
+    /// Every line is a row of the terminal, any char isn't a braille code is thought as empty
+    pub fn from_braille(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let cols = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut sprite = Self::new(cols * 2, lines.len() * 4);
+        let top = sprite.height;
+        for (row, line) in lines.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let Some(pixel) = make_braille(c) else {
+                    continue;
+                };
+                for dy in 0..4 {
+                    for dx in 0..2 {
+                        if pixel.is_set(dx, dy) {
+                            let (x, y) = (col * 2 + dx, top - 1 - (row * 4 + dy));
+                            sprite.dots[y * sprite.width + x] = Some(Color::Reset);
+                        }
+                    }
+                }
+            }
+        }
+        sprite
+    }
+
+    /// Return the width in dots
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Return the height in dots
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Set the dot on *(x, y)*
+    ///
+    /// Do nothing if the *(x, y)* is out of the sprite
+    pub fn set(&mut self, x: usize, y: usize) {
+        self.set_at(x, y, Some(Color::Reset));
+    }
+
+    /// Similar to [`set`](struct.Sprite.html#method.set), but it's support color
+    pub fn set_colorful(&mut self, x: usize, y: usize, color: Color) {
+        self.set_at(x, y, Some(color));
+    }
+
+    /// Unset the dot on *(x, y)*
+    pub fn unset(&mut self, x: usize, y: usize) {
+        self.set_at(x, y, None);
+    }
+
+    /// Return the dot on *(x, y)*
+    ///
+    /// `None` means there isn't a dot, `Some(Color::Reset)` means a dot without color
+    pub fn get(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.dots[y * self.width + x]
+    }
+
+    /// Set the color of all the dots
+    pub fn fill_color(&mut self, color: Color) {
+        for dot in self.dots.iter_mut().flatten() {
+            *dot = color;
+        }
+    }
+
+    /// Make the sprite transparent or not
+    ///
+    /// The default is `true`, the empty dots won't change the canvas.
+    /// If it's `false`, the empty dots will erase the dots on the canvas.
+    pub fn transparent(&mut self, transparent: bool) {
+        self.transparent = transparent;
+    }
+
+    /// Flip the sprite from left to right
+    pub fn flip_horizontal(&mut self) {
+        for row in self.dots.chunks_mut(self.width.max(1)) {
+            row.reverse();
+        }
+    }
+
+    /// Flip the sprite from top to bottom
+    pub fn flip_vertical(&mut self) {
+        let (w, h) = (self.width, self.height);
+        for y in 0..h / 2 {
+            for x in 0..w {
+                self.dots.swap(y * w + x, (h - 1 - y) * w + x);
+            }
+        }
+    }
+
+    /// Rotate the sprite clockwise by 90° steps
+    ///
+    /// * `quarter` - how many 90° to rotate, negative number for counterclockwise
+    pub fn rotate(&mut self, quarter: i32) {
+        for _ in 0..quarter.rem_euclid(4) {
+            self.rotate_once();
+        }
+    }
+
+    fn rotate_once(&mut self) {
+        let (w, h) = (self.width, self.height);
+        let mut dots = vec![None; w * h];
+        // (x, y) -> (y, w - 1 - x) and the new size is (h, w)
+        for y in 0..h {
+            for x in 0..w {
+                dots[(w - 1 - x) * h + y] = self.dots[y * w + x];
+            }
+        }
+        self.width = h;
+        self.height = w;
+        self.dots = dots;
+    }
+
+    /// Check if any dot of the two sprites are on the same place
+    ///
+    /// * `xy` - where the sprite is stamped, it's the left bottom corner like [`Canvas::blit`](struct.Canvas.html#method.blit)
+    /// * `other_xy` - where the other sprite is stamped
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use rsille::Sprite;
+    /// let block = Sprite::from_braille("⣿");
+    /// let corner = Sprite::from_braille("⠁");
+    /// assert!(block.collides((0, 0), &corner, (1, 0)));
+    /// // only the left top dot of the corner is set, it's above the block
+    /// assert!(!block.collides((0, 0), &corner, (0, 1)));
+    /// assert!(!block.collides((0, 0), &corner, (2, 0)));
+    /// ```
+    pub fn collides<T>(&self, xy: (T, T), other: &Sprite, other_xy: (T, T)) -> bool
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (round(xy.0), round(xy.1));
+        let (ox, oy) = (round(other_xy.0), round(other_xy.1));
+        // the overlapped box in the dots of self
+        let left = (ox - x).max(0);
+        let bottom = (oy - y).max(0);
+        let right = (ox - x + other.width as i32).min(self.width as i32);
+        let top = (oy - y + other.height as i32).min(self.height as i32);
+        for sy in bottom..top {
+            for sx in left..right {
+                let (tx, ty) = ((sx + x - ox) as usize, (sy + y - oy) as usize);
+                if self.get(sx as usize, sy as usize).is_some() && other.get(tx, ty).is_some() {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn set_at(&mut self, x: usize, y: usize, dot: Option<Color>) {
+        if x < self.width && y < self.height {
+            self.dots[y * self.width + x] = dot;
+        }
+    }
+}
+
+impl Paint for Sprite {
+    fn paint<T>(&self, canvas: &mut Canvas, x: T, y: T) -> Result<(), RsilleErr>
+    where
+        T: Into<f64>,
+    {
+        let (x, y) = (round(x) as f64, round(y) as f64);
+        let dots = self.dots.iter().enumerate().filter_map(|(i, &dot)| {
+            let (px, py) = (x + (i % self.width) as f64, y + (i / self.width) as f64);
+            (dot.is_some() || !self.transparent).then_some((px, py, dot))
+        });
+        canvas.stamp(dots);
+        Ok(())
+    }
+}
@@ -5,6 +5,8 @@
 //! Many functions put some escape sequences to the stdout,
 //! so use them only when you know what you are doing
 
+use std::sync::OnceLock;
+
 use crossterm::{cursor, execute, terminal};
 
 /// Get the *(width, height)* of terminal
@@ -36,6 +38,70 @@ pub fn show_cursor() {
     execute!(std::io::stdout(), cursor::Show).unwrap();
 }
 
+/// Restore the terminal, disable the raw mode and show the cursor
+///
+/// It never panics, so it's safe to call it in the panic hook
+pub fn restore() {
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(std::io::stdout(), cursor::Show);
+}
+
+/// The way to show the images on the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Graphics {
+    /// The braille code, every terminal can show it
+    Braille,
+    /// The [kitty graphics protocol](https://sw.kovidgoyal.net/kitty/graphics-protocol/), kitty, wezterm, ghostty and konsole
+    Kitty,
+    /// The sixel, foot, mlterm, xterm with the sixel and some others
+    Sixel,
+}
+
+/// Guess the best way to show the images on the terminal
+///
+/// It only checks the environment variables like `TERM` and `TERM_PROGRAM`,
+/// so it's never blocked but it can be wrong, it's always [`Graphics::Braille`] if not sure.
+pub fn graphics() -> Graphics {
+    let var = |name| std::env::var(name).unwrap_or_default().to_lowercase();
+    let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+    if std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || std::env::var_os("KONSOLE_VERSION").is_some()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || program == "wezterm"
+        || program == "ghostty"
+    {
+        Graphics::Kitty
+    } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+        Graphics::Sixel
+    } else {
+        Graphics::Braille
+    }
+}
+
+/// Check if the terminal can show the double and curly underline and the underline color
+///
+/// Like [`graphics`], it only checks the environment variables and it's `false` if not sure.
+/// It's checked once and remembered.
+pub fn styled_underline() -> bool {
+    static STYLED: OnceLock<bool> = OnceLock::new();
+    *STYLED.get_or_init(|| {
+        let var = |name| std::env::var(name).unwrap_or_default().to_lowercase();
+        let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+        // the vte 0.51 of gnome terminal and the others
+        let vte = var("VTE_VERSION").parse::<u32>().is_ok_and(|v| v >= 5102);
+        vte || std::env::var_os("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || term.starts_with("foot")
+            || term.starts_with("alacritty")
+            || matches!(
+                program.as_str(),
+                "wezterm" | "ghostty" | "iterm.app" | "mintty"
+            )
+    })
+}
+
 /// Move cursor to *(x, y)*
 pub fn move_to(x: u32, y: u32) {
     execute!(std::io::stdout(), cursor::MoveTo(x as u16, y as u16)).unwrap();
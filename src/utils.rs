@@ -1,11 +1,18 @@
 use core::fmt;
-use std::error::Error;
+use std::{error::Error, io, sync::Arc};
 
 use crate::braille::Pixel;
 
 pub const MIN_ZOOM: f64 = 0.001;
 pub const MIN_DIFFERENCE: f64 = 1E-10;
 
+// the ordered dithering matrix, decide which dots are drawn for a density
+#[rustfmt::skip]
+pub(crate) const BAYER: [[f64; 4]; 4] = [[ 0.0,  8.0,  2.0, 10.0],
+                                         [12.0,  4.0, 14.0,  6.0],
+                                         [ 3.0, 11.0,  1.0,  9.0],
+                                         [15.0,  7.0, 13.0,  5.0]];
+
 pub(crate) fn round<T>(v: T) -> i32
 where
     T: Into<f64>,
@@ -24,15 +31,42 @@ pub(crate) fn check_zoom(v: f64) {
     }
 }
 
-// the the (col, row) of (x, y)
-pub fn get_pos<T>(x: T, y: T) -> (i32, i32)
+// the the (col, row) of (x, y), the cell has `size` of (columns, rows) dots
+pub fn get_pos<T>(x: T, y: T, size: (u8, u8)) -> (i32, i32)
 where
     T: Into<f64>,
 {
     let (x, y) = (round(x), round(y));
-    let row = if y < 0 { (y + 1) / 4 - 1 } else { y / 4 };
-    let col = if x < 0 { (x - 1) / 2 } else { x / 2 };
-    (col, row)
+    (x.div_euclid(size.0 as i32), y.div_euclid(size.1 as i32))
+}
+
+// the cells a char takes in the terminal, 2 for the wide chars like CJK and emoji,
+// 0 for the combining marks, it isn't the full unicode table but enough for the canvas
+pub(crate) fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
 }
 
 #[allow(unused)]
@@ -45,27 +79,171 @@ pub(crate) fn make_braille(c: char) -> Option<Pixel> {
     }
 }
 
+// the xorshift64, it's enough for the random things of art
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        // xorshift can't work with 0
+        Self(seed.max(1))
+    }
+
+    // a number in [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    // a number in [0, n), n must bigger than 0
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+}
+
 /// The error type used by this crate
+///
+/// Match the [`kind`](struct.RsilleErr.html#method.kind) to know what's wrong,
+/// and the [`source`](https://doc.rust-lang.org/std/error/trait.Error.html#method.source) is the error causes it, like the io error.
+///
+/// ## Example
+///
+/// ```
+/// use std::error::Error;
+/// use rsille::{extra::LifeGame, ErrKind};
+/// let err = LifeGame::from_path("no/such/file.rle").unwrap_err();
+/// assert_eq!(err.kind(), &ErrKind::Io);
+/// assert_eq!(err.to_string(), "can't open rle file: no/such/file.rle");
+/// assert!(err.source().is_some());
+/// ```
 #[derive(Debug, Clone)]
 pub struct RsilleErr {
+    kind: ErrKind,
     msg: String,
+    hint: Option<String>,
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+/// What's wrong of the [`RsilleErr`](struct.RsilleErr.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrKind {
+    /// Can't read or write the file or the terminal
+    Io,
+    /// The file or the string isn't in the right format, like the rle, obj and `.rsille`
+    Parse,
+    /// The index or the size doesn't fit the available, like the vertices of an object or a face
+    OutOfBounds {
+        /// the index or the size wanted
+        requested: usize,
+        /// the count can be used
+        available: usize,
+    },
+    /// The thing asked isn't there, like the layer or the frames
+    NotFound,
+    /// Any other error
+    Other,
 }
 
 impl RsilleErr {
-    /// Return a new RsilleErr
+    /// Return a new RsilleErr, the kind is [`ErrKind::Other`](enum.ErrKind.html#variant.Other)
     pub fn new(msg: String) -> Self {
-        Self { msg }
+        Self::with_kind(ErrKind::Other, msg)
+    }
+
+    /// Return a new RsilleErr of the kind
+    pub fn with_kind(kind: ErrKind, msg: String) -> Self {
+        Self {
+            kind,
+            msg,
+            hint: None,
+            source: None,
+        }
     }
 
     /// Transform other error type to RsilleErr
+    ///
+    /// Only the message is kept, the io error should use `RsilleErr::from` to keep the source
     pub fn to_rsille_err<E: Error>(e: E) -> RsilleErr {
         RsilleErr::new(e.to_string())
     }
+
+    /// Wrap the error with the message, the kind is kept and the old error is the source
+    pub fn context(self, msg: String) -> Self {
+        Self {
+            kind: self.kind.clone(),
+            msg,
+            hint: None,
+            source: Some(Arc::new(self)),
+        }
+    }
+
+    /// Tell how to fix it
+    pub fn with_hint(mut self, hint: &str) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    /// Return the kind of the error
+    pub fn kind(&self) -> &ErrKind {
+        &self.kind
+    }
+
+    /// Return how to fix it if there is a hint
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_deref()
+    }
 }
 
 impl fmt::Display for RsilleErr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.msg)
+        write!(f, "{}", self.msg)?;
+        if let Some(hint) = &self.hint {
+            write!(f, " (hint: {})", hint)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RsilleErr {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+#[cfg(any(feature = "img", feature = "export"))]
+impl From<image::ImageError> for RsilleErr {
+    fn from(e: image::ImageError) -> Self {
+        let kind = match &e {
+            image::ImageError::IoError(_) => ErrKind::Io,
+            image::ImageError::Decoding(_) => ErrKind::Parse,
+            _ => ErrKind::Other,
+        };
+        Self {
+            kind,
+            msg: e.to_string(),
+            hint: None,
+            source: Some(Arc::new(e)),
+        }
+    }
+}
+
+impl From<io::Error> for RsilleErr {
+    fn from(e: io::Error) -> Self {
+        Self {
+            kind: ErrKind::Io,
+            msg: e.to_string(),
+            hint: None,
+            source: Some(Arc::new(e)),
+        }
     }
 }
 